@@ -17,8 +17,8 @@ const rpc = new Server("https://mainnet.sorobanrpc.com");
 const CONTRACT_ID = "CDL74RF5BLYR2YBLCCI7F5FB6TPSCLKEJUBSD2RSVWZ4YHF3VMFAIGWA"
 const FARMER_PK = "GBIIUZH63Z262QXGKJIP3ZU5DS7L4L2TBTYGPXRIGQXZAF25A72YNULL"
 
-export async function getFarmBlock() {
-  let block
+export async function getFarmIndex() {
+  let index
 
   await rpc.getContractData(
     CONTRACT_ID,
@@ -32,45 +32,49 @@ export async function getFarmBlock() {
       return storage?.map((entry) => {
           const key: string = scValToNative(entry.key())[0]
 
-          if (key === 'FarmBlock') {
-              block = scValToNative(entry.val())
+          if (key === 'FarmIndex') {
+              index = scValToNative(entry.val())
           }
       })
   })
 
-  console.log('FarmBlock', block);
+  console.log('FarmIndex', index);
 }
 
-async function getBlock(index: number) {
-    let block;
+// Blocks and pails opened since the upgrade live under the `V2` keys, older ones keep their original keys
+async function getEntry(keys: string[], args: xdr.ScVal[]) {
+    for (const key of keys) {
+        try {
+            const { val } = await rpc.getContractData(CONTRACT_ID, xdr.ScVal.scvVec([
+                xdr.ScVal.scvSymbol(key),
+                ...args
+            ]), Durability.Temporary)
+
+            return scValToNative(val.contractData().val())
+        } catch {
+            continue
+        }
+    }
+}
 
-    await rpc.getContractData(CONTRACT_ID, xdr.ScVal.scvVec([
-        xdr.ScVal.scvSymbol('Block'),
+async function getBlock(index: number) {
+    const block = await getEntry(['BlockV2', 'Block'], [
         xdr.ScVal.scvU32(Number(index))
-    ]), Durability.Temporary)
-        .then(({ val }) => {
-            block = scValToNative(val.contractData().val())
-        })
+    ])
 
     console.log('Block', block);
 }
 
 async function getPail(index: number) {
-  let pail;
-
-  await rpc.getContractData(CONTRACT_ID, xdr.ScVal.scvVec([
-      xdr.ScVal.scvSymbol('Pail'),
+  const pail = await getEntry(['PailV2', 'Pail'], [
       Address.fromString(FARMER_PK).toScVal(),
       xdr.ScVal.scvU32(Number(index))
-  ]), Durability.Temporary)
-      .then(({ val }) => {
-          pail = scValToNative(val.contractData().val())
-      })
+  ])
 
   console.log('Pail', pail);
 }
 
-await getFarmBlock()
+await getFarmIndex()
 await getBlock(Number(values.block))
 await getPail(Number(values.block))
 
@@ -0,0 +1,101 @@
+use soroban_sdk::{
+    contractimpl, panic_with_error, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec,
+};
+
+use kale_types::FarmConfig;
+
+use crate::{
+    errors::Errors,
+    storage::{
+        extend_instance_ttl, get_factory_admin, get_factory_wasm, get_farm, get_farm_count,
+        has_factory_admin, has_farm_id, set_factory_admin, set_factory_wasm, set_farm,
+        set_farm_count, set_farm_id,
+    },
+    types::FarmEntry,
+    Contract, ContractArgs, ContractClient, FactoryTrait, FARMS_PAGE,
+};
+
+#[contractimpl]
+impl FactoryTrait for Contract {
+    fn __constructor(env: Env, admin: Address, wasm_hash: BytesN<32>) {
+        if has_factory_admin(&env) {
+            panic_with_error!(&env, &Errors::FactoryExists);
+        }
+
+        set_factory_admin(&env, &admin);
+        set_factory_wasm(&env, &wasm_hash);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn set_wasm(env: Env, wasm_hash: BytesN<32>) {
+        get_factory_admin(&env).require_auth();
+
+        // Only affects future deploys, existing farms are upgraded by their own homesteader
+        set_factory_wasm(&env, &wasm_hash);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn deploy(
+        env: Env,
+        homesteader: Address,
+        asset: Address,
+        salt: BytesN<32>,
+        config: Option<FarmConfig>,
+    ) -> Address {
+        homesteader.require_auth();
+
+        // Scope the salt to the homesteader so nobody can front-run another homesteader's farm address
+        let mut salt_bytes = Bytes::new(&env);
+
+        salt_bytes.append(&homesteader.clone().to_xdr(&env));
+        salt_bytes.append(&salt.into());
+
+        let salt = env.crypto().keccak256(&salt_bytes);
+
+        // NOTE the farm mints `asset` so the SAC admin must be handed over to the returned farm address before the first `harvest`
+        let farm = env
+            .deployer()
+            .with_current_contract(salt)
+            .deploy_v2(get_factory_wasm(&env), (&homesteader, &asset, config));
+
+        let index = get_farm_count(&env);
+
+        set_farm(
+            &env,
+            index,
+            &FarmEntry {
+                farm: farm.clone(),
+                asset: asset.clone(),
+                homesteader: homesteader.clone(),
+            },
+        );
+        set_farm_id(&env, farm.clone(), index);
+        set_farm_count(&env, index + 1);
+
+        env.events()
+            .publish((symbol_short!("deploy"), homesteader, asset), farm.clone());
+
+        extend_instance_ttl(&env);
+
+        farm
+    }
+
+    fn farms(env: Env, start: u32, limit: u32) -> Vec<FarmEntry> {
+        let end = get_farm_count(&env).min(start.saturating_add(limit.min(FARMS_PAGE)));
+        let mut farms = Vec::new(&env);
+
+        for index in start..end {
+            if let Some(entry) = get_farm(&env, index) {
+                farms.push_back(entry);
+            }
+        }
+
+        farms
+    }
+
+    fn is_farm(env: Env, farm: Address) -> bool {
+        has_farm_id(&env, farm)
+    }
+}
@@ -0,0 +1,35 @@
+#![no_std]
+
+use kale_types::FarmConfig;
+use soroban_sdk::{contract, Address, BytesN, Env, Vec};
+use types::FarmEntry;
+
+mod contract_factory;
+mod errors;
+mod storage;
+mod tests;
+mod types;
+
+pub const WEEK_OF_LEDGERS: u32 = 60 * 60 * 24 / 5 * 7; // assumes 5 second ledger close times
+pub const FARMS_PAGE: u32 = 50; // most entries a single `farms` call reads
+
+#[contract]
+pub struct Contract;
+
+pub trait FactoryTrait {
+    fn __constructor(env: Env, admin: Address, wasm_hash: BytesN<32>);
+
+    fn set_wasm(env: Env, wasm_hash: BytesN<32>);
+
+    fn deploy(
+        env: Env,
+        homesteader: Address,
+        asset: Address,
+        salt: BytesN<32>,
+        config: Option<FarmConfig>,
+    ) -> Address;
+
+    fn farms(env: Env, start: u32, limit: u32) -> Vec<FarmEntry>;
+
+    fn is_farm(env: Env, farm: Address) -> bool;
+}
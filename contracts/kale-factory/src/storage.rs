@@ -0,0 +1,94 @@
+use soroban_sdk::{panic_with_error, Address, BytesN, Env};
+
+use crate::{
+    errors::Errors,
+    types::{FarmEntry, Storage},
+    WEEK_OF_LEDGERS,
+};
+
+pub fn extend_instance_ttl(env: &Env) {
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .instance()
+        .extend_ttl(max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+
+pub fn extend_persistent_ttl(env: &Env, key: &Storage) {
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .persistent()
+        .extend_ttl(key, max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+
+pub fn has_factory_admin(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .has::<Storage>(&Storage::FactoryAdmin)
+}
+pub fn get_factory_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::FactoryAdmin)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::FactoryMissing))
+}
+pub fn set_factory_admin(env: &Env, admin: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::FactoryAdmin, admin);
+}
+
+pub fn get_factory_wasm(env: &Env) -> BytesN<32> {
+    env.storage()
+        .instance()
+        .get::<Storage, BytesN<32>>(&Storage::FactoryWasm)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::FactoryMissing))
+}
+pub fn set_factory_wasm(env: &Env, wasm_hash: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set::<Storage, BytesN<32>>(&Storage::FactoryWasm, wasm_hash);
+}
+
+pub fn get_farm_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get::<Storage, u32>(&Storage::FarmCount)
+        .unwrap_or(0)
+}
+pub fn set_farm_count(env: &Env, count: u32) {
+    env.storage()
+        .instance()
+        .set::<Storage, u32>(&Storage::FarmCount, &count);
+}
+
+pub fn get_farm(env: &Env, index: u32) -> Option<FarmEntry> {
+    env.storage()
+        .persistent()
+        .get::<Storage, FarmEntry>(&Storage::Farm(index))
+}
+pub fn set_farm(env: &Env, index: u32, entry: &FarmEntry) {
+    let farm_key = Storage::Farm(index);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, FarmEntry>(&farm_key, entry);
+
+    extend_persistent_ttl(env, &farm_key);
+}
+
+pub fn has_farm_id(env: &Env, farm: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has::<Storage>(&Storage::FarmId(farm))
+}
+pub fn set_farm_id(env: &Env, farm: Address, index: u32) {
+    let farm_id_key = Storage::FarmId(farm);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, u32>(&farm_id_key, &index);
+
+    extend_persistent_ttl(env, &farm_id_key);
+}
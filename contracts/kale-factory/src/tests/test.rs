@@ -0,0 +1,130 @@
+extern crate std;
+
+use crate::{Contract, ContractClient, FARMS_PAGE};
+use kale_sc::{default_farm_config, types::FarmConfig};
+use soroban_sdk::{
+    testutils::{Address as _, BytesN as _, EnvTestConfig, Ledger},
+    token, Address, BytesN, Env,
+};
+
+// The factory deploys the real farm wasm rather than a native registration, `make test` builds it first
+const KALE_SC_WASM: &[u8] = include_bytes!("../../../../target/wasm32v1-none/release/kale_sc.wasm");
+
+#[test]
+fn test() {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.ledger().set_min_temp_entry_ttl(17280);
+    env.ledger().set_min_persistent_entry_ttl(2073600);
+
+    env.mock_all_auths();
+
+    let wasm_hash = env.deployer().upload_contract_wasm(KALE_SC_WASM);
+
+    let admin = Address::generate(&env);
+    let factory_address = env.register(Contract, (&admin, &wasm_hash));
+    let factory_client = ContractClient::new(&env, &factory_address);
+
+    let homesteader = Address::generate(&env);
+    let asset_address = env
+        .register_stellar_asset_contract_v2(homesteader.clone())
+        .address();
+    let asset_admin = token::StellarAssetClient::new(&env, &asset_address);
+
+    let config = FarmConfig {
+        block_interval: 60,
+        block_reward: 100_0000000,
+        interval_min: 60,
+        interval_max: 60,
+        ..default_farm_config(&env)
+    };
+    let salt = BytesN::random(&env);
+
+    let farm_address = factory_client.deploy(&homesteader, &asset_address, &salt, &Some(config));
+    let farm_client = kale_sc::ContractClient::new(&env, &farm_address);
+
+    asset_admin.set_admin(&farm_address);
+
+    // The farm was constructed with the caller's parameters
+    let stored = env.as_contract(&farm_address, || {
+        env.storage()
+            .instance()
+            .get::<_, kale_sc::types::FarmConfig>(&kale_sc::types::Storage::FarmConfig)
+            .unwrap()
+    });
+
+    assert_eq!(stored.block_interval, 60);
+    assert_eq!(stored.block_reward, 100_0000000);
+
+    // and rolls over on its own interval
+    let farmer = Address::generate(&env);
+    let timestamp = env.ledger().timestamp();
+
+    farm_client.plant(&farmer, &0);
+    env.ledger().set_timestamp(timestamp + 60);
+    farm_client.plant(&Address::generate(&env), &0);
+
+    let index = env.as_contract(&farm_address, || {
+        env.storage()
+            .instance()
+            .get::<_, u32>(&kale_sc::types::Storage::FarmIndex)
+            .unwrap()
+    });
+
+    assert_eq!(index, 1);
+
+    // A farm without a config for a second community
+    let other_asset = Address::generate(&env);
+    let other_farm =
+        factory_client.deploy(&homesteader, &other_asset, &BytesN::random(&env), &None);
+
+    // Reusing a salt is rejected, reusing it as another homesteader is not
+    assert!(factory_client
+        .try_deploy(&homesteader, &asset_address, &salt, &None)
+        .is_err());
+
+    let third_farm = factory_client.deploy(&Address::generate(&env), &asset_address, &salt, &None);
+
+    let farms = factory_client.farms(&0, &10);
+
+    assert_eq!(farms.len(), 3);
+    assert_eq!(farms.get_unchecked(0).farm, farm_address);
+    assert_eq!(farms.get_unchecked(0).asset, asset_address);
+    assert_eq!(farms.get_unchecked(1).farm, other_farm);
+    assert_eq!(farms.get_unchecked(2).farm, third_farm);
+    assert_eq!(factory_client.farms(&1, &1).len(), 1);
+    assert_eq!(factory_client.farms(&3, &10).len(), 0);
+
+    assert!(factory_client.is_farm(&farm_address));
+    assert!(!factory_client.is_farm(&asset_address));
+}
+
+#[test]
+fn test_farms_page() {
+    let env: Env = Env::default();
+
+    env.mock_all_auths();
+
+    let wasm_hash = env.deployer().upload_contract_wasm(KALE_SC_WASM);
+    let factory_address = env.register(Contract, (&Address::generate(&env), &wasm_hash));
+    let factory_client = ContractClient::new(&env, &factory_address);
+
+    let homesteader = Address::generate(&env);
+
+    for _ in 0..=FARMS_PAGE {
+        factory_client.deploy(
+            &homesteader,
+            &Address::generate(&env),
+            &BytesN::random(&env),
+            &None,
+        );
+    }
+
+    // However many are asked for, a page never reads more than `FARMS_PAGE` entries
+    assert_eq!(factory_client.farms(&0, &u32::MAX).len(), FARMS_PAGE);
+    assert_eq!(factory_client.farms(&FARMS_PAGE, &u32::MAX).len(), 1);
+}
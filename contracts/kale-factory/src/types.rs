@@ -0,0 +1,19 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FarmEntry {
+    pub farm: Address,
+    pub asset: Address,
+    pub homesteader: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Storage {
+    FactoryAdmin,    // : address
+    FactoryWasm,     // : BytesN<32>
+    FarmCount,       // : u32
+    Farm(u32),       // (index) : FarmEntry
+    FarmId(Address), // (farm) : u32
+}
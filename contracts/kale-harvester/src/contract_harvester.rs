@@ -0,0 +1,67 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env, Vec};
+
+use crate::{
+    errors::Errors,
+    storage::{
+        extend_instance_ttl, get_harvester_asset, get_harvester_farm, get_keeper_fee,
+        has_harvester_farm, set_harvester_asset, set_harvester_farm, set_keeper_fee,
+    },
+    Contract, ContractArgs, ContractClient, FarmClient, HarvesterTrait, BASIS_POINTS,
+};
+
+#[contractimpl]
+impl HarvesterTrait for Contract {
+    fn __constructor(env: Env, farm: Address, asset: Address, fee: u32) {
+        if has_harvester_farm(&env) {
+            panic_with_error!(&env, &Errors::HarvesterExists);
+        }
+
+        if fee > BASIS_POINTS {
+            panic_with_error!(&env, &Errors::KeeperFeeTooHigh);
+        }
+
+        set_harvester_farm(&env, &farm);
+        set_harvester_asset(&env, &asset);
+        set_keeper_fee(&env, fee);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn harvest(env: Env, keeper: Address, pails: Vec<(Address, u32)>) -> Vec<Option<i128>> {
+        // No auth_require here, `harvest` on the farm is permissionless and fees can only ever flow to the `keeper`
+
+        let farm = FarmClient::new(&env, &get_harvester_farm(&env));
+        let asset = token::Client::new(&env, &get_harvester_asset(&env));
+        let fee = get_keeper_fee(&env) as i128;
+        let harvester = env.current_contract_address();
+
+        let mut rewards = Vec::new(&env);
+
+        for (farmer, index) in pails {
+            // Skip any pail the farm refuses (e.g. `PailMissing`, `HarvestNotReady`, `WorkMissing`) rather than failing the whole batch
+            let reward = match farm.try_harvest(&farmer, &index) {
+                Ok(Ok(reward)) => reward,
+                _ => {
+                    rewards.push_back(None);
+                    continue;
+                }
+            };
+
+            let keeper_fee = reward
+                .max(0)
+                .fixed_mul_floor(&env, &fee, &(BASIS_POINTS as i128));
+
+            // The fee is only collected if the farmer has granted the harvester a sufficient allowance
+            if keeper_fee > 0 {
+                let _ = asset.try_transfer_from(&harvester, &farmer, &keeper, &keeper_fee);
+            }
+
+            rewards.push_back(Some(reward));
+        }
+
+        extend_instance_ttl(&env);
+
+        rewards
+    }
+}
@@ -0,0 +1,9 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Errors {
+    HarvesterExists = 1,
+    HarvesterMissing = 2,
+    KeeperFeeTooHigh = 3,
+}
@@ -0,0 +1,27 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractclient, Address, Env, Vec};
+
+mod contract_harvester;
+mod errors;
+mod storage;
+mod tests;
+mod types;
+
+pub const BASIS_POINTS: u32 = 10_000; // 100%
+pub const WEEK_OF_LEDGERS: u32 = 60 * 60 * 24 / 5 * 7; // assumes 5 second ledger close times
+
+#[contract]
+pub struct Contract;
+
+// The subset of the kale-sc `FarmTrait` the harvester calls
+#[contractclient(name = "FarmClient")]
+pub trait FarmInterface {
+    fn harvest(env: Env, farmer: Address, index: u32) -> i128;
+}
+
+pub trait HarvesterTrait {
+    fn __constructor(env: Env, farm: Address, asset: Address, fee: u32);
+
+    fn harvest(env: Env, keeper: Address, pails: Vec<(Address, u32)>) -> Vec<Option<i128>>;
+}
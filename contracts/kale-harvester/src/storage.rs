@@ -0,0 +1,52 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{errors::Errors, types::Storage, WEEK_OF_LEDGERS};
+
+pub fn extend_instance_ttl(env: &Env) {
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .instance()
+        .extend_ttl(max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+
+pub fn has_harvester_farm(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .has::<Storage>(&Storage::HarvesterFarm)
+}
+pub fn get_harvester_farm(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::HarvesterFarm)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::HarvesterMissing))
+}
+pub fn set_harvester_farm(env: &Env, farm: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::HarvesterFarm, farm);
+}
+
+pub fn get_harvester_asset(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::HarvesterAsset)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::HarvesterMissing))
+}
+pub fn set_harvester_asset(env: &Env, asset: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::HarvesterAsset, asset);
+}
+
+pub fn get_keeper_fee(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get::<Storage, u32>(&Storage::KeeperFee)
+        .unwrap_or(0)
+}
+pub fn set_keeper_fee(env: &Env, fee: u32) {
+    env.storage()
+        .instance()
+        .set::<Storage, u32>(&Storage::KeeperFee, &fee);
+}
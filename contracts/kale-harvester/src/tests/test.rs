@@ -0,0 +1,141 @@
+extern crate std;
+
+use crate::{Contract, ContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env,
+};
+
+fn generate_hash(
+    env: &Env,
+    index: &u32,
+    nonce: &u64,
+    entropy: &BytesN<32>,
+    farmer: &Address,
+) -> BytesN<32> {
+    let mut hash_array = [0u8; 76];
+
+    let mut farmer_array = [0u8; 32];
+    let farmer_bytes = farmer.clone().to_xdr(env);
+    farmer_bytes
+        .slice(farmer_bytes.len() - 32..)
+        .copy_into_slice(&mut farmer_array);
+
+    hash_array[..4].copy_from_slice(&index.to_be_bytes());
+    hash_array[4..12].copy_from_slice(&nonce.to_be_bytes());
+    hash_array[12..44].copy_from_slice(&entropy.to_array());
+    hash_array[44..].copy_from_slice(&farmer_array);
+
+    env.crypto()
+        .keccak256(&Bytes::from_array(env, &hash_array))
+        .to_bytes()
+}
+
+#[test]
+fn test() {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.ledger().set_min_temp_entry_ttl(17280);
+    env.ledger().set_min_persistent_entry_ttl(2073600);
+
+    env.mock_all_auths();
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    let homesteader: Address = Address::generate(&env);
+    let keeper: Address = Address::generate(&env);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(homesteader.clone());
+    let asset_address = asset_sac.address();
+    let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
+    let asset_client = token::Client::new(&env, &asset_address);
+
+    let farm_address: Address = env.register(
+        kale_sc::Contract,
+        (
+            &homesteader,
+            &asset_address,
+            None::<kale_sc::types::FarmConfig>,
+        ),
+    );
+    let farm_client = kale_sc::ContractClient::new(&env, &farm_address);
+
+    asset_homesteader.set_admin(&farm_address);
+
+    // 5% keeper fee
+    let harvester_address: Address =
+        env.register(Contract, (&farm_address, &asset_address, &500u32));
+    let harvester_client = ContractClient::new(&env, &harvester_address);
+
+    let farmer_1: Address = Address::generate(&env);
+    let farmer_2: Address = Address::generate(&env);
+    let farmer_3: Address = Address::generate(&env);
+
+    farm_client.plant(&farmer_1, &0);
+    farm_client.plant(&farmer_2, &0);
+    farm_client.plant(&farmer_3, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    // Genesis block, index 0 with empty entropy
+    let entropy = BytesN::from_array(&env, &[0; 32]);
+
+    farm_client.work(
+        &farmer_1,
+        &generate_hash(&env, &0, &0, &entropy, &farmer_1),
+        &0,
+    );
+    farm_client.work(
+        &farmer_2,
+        &generate_hash(&env, &0, &0, &entropy, &farmer_2),
+        &0,
+    );
+
+    env.ledger()
+        .set_timestamp(timestamp + kale_sc::BLOCK_INTERVAL);
+
+    farm_client.plant(&farmer_1, &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + kale_sc::GRACE_PERIOD);
+
+    // Only farmer 1 opts into paying the keeper
+    asset_client.approve(
+        &farmer_1,
+        &harvester_address,
+        &i128::MAX,
+        &(env.ledger().sequence() + 100),
+    );
+
+    let rewards = harvester_client.harvest(
+        &keeper,
+        &vec![
+            &env,
+            (farmer_1.clone(), 0),
+            (farmer_2.clone(), 0),
+            (farmer_3.clone(), 0), // WorkMissing
+            (farmer_1.clone(), 0), // PailMissing
+            (farmer_1.clone(), 1), // HarvestNotReady
+        ],
+    );
+
+    let reward_1 = rewards.get_unchecked(0).unwrap();
+    let reward_2 = rewards.get_unchecked(1).unwrap();
+    let fee_1 = reward_1 * 500 / 10_000;
+
+    assert!(reward_1 > 0 && reward_2 > 0);
+    assert_eq!(rewards.get_unchecked(2), None);
+    assert_eq!(rewards.get_unchecked(3), None);
+    assert_eq!(rewards.get_unchecked(4), None);
+
+    assert_eq!(asset_client.balance(&keeper), fee_1);
+    assert_eq!(asset_client.balance(&farmer_1), reward_1 - fee_1);
+    assert_eq!(asset_client.balance(&farmer_2), reward_2);
+}
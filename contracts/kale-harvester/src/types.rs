@@ -0,0 +1,9 @@
+use soroban_sdk::contracttype;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Storage {
+    HarvesterFarm,  // : address
+    HarvesterAsset, // : address
+    KeeperFee,      // : u32
+}
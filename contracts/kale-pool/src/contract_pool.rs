@@ -0,0 +1,241 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractimpl, panic_with_error, symbol_short, token, vec, Address, BytesN, Env, IntoVal,
+};
+
+use crate::{
+    errors::Errors,
+    storage::{
+        extend_instance_ttl, get_operator, get_operator_fee, get_planted, get_pool_asset,
+        get_pool_farm, get_pool_planted, get_pool_rewards, get_pool_shares, get_shares,
+        has_operator, remove_planted, set_operator, set_operator_fee, set_planted, set_pool_asset,
+        set_pool_farm, set_pool_planted, set_pool_rewards, set_pool_shares, set_shares,
+    },
+    Contract, ContractArgs, ContractClient, FarmClient, OperatorTrait, PoolTrait, BASIS_POINTS,
+    MINIMUM_SHARES,
+};
+
+#[contractimpl]
+impl PoolTrait for Contract {
+    fn __constructor(env: Env, operator: Address, farm: Address, asset: Address, fee: u32) {
+        operator.require_auth();
+
+        if has_operator(&env) {
+            panic_with_error!(&env, &Errors::PoolExists);
+        }
+
+        if fee > BASIS_POINTS {
+            panic_with_error!(&env, &Errors::OperatorFeeTooHigh);
+        }
+
+        set_operator(&env, &operator);
+        set_operator_fee(&env, fee);
+        set_pool_farm(&env, &farm);
+        set_pool_asset(&env, &asset);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn deposit(env: Env, farmer: Address, amount: i128) -> i128 {
+        farmer.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, &Errors::DepositAmountTooLow);
+        }
+
+        let total_shares = get_pool_shares(&env);
+
+        // Price new shares against everything the pool owns, including stake currently planted in the farm
+        // The first deposit locks `MINIMUM_SHARES` away for good, so a donation can't make a single share worth more than the next deposit
+        let (shares, locked) = if total_shares == 0 {
+            (amount - MINIMUM_SHARES, MINIMUM_SHARES)
+        } else {
+            (
+                amount.fixed_mul_floor(&env, &total_shares, &total_assets(&env).max(1)),
+                0,
+            )
+        };
+
+        if shares <= 0 {
+            panic_with_error!(&env, &Errors::DepositAmountTooLow);
+        }
+
+        token::Client::new(&env, &get_pool_asset(&env)).transfer(
+            &farmer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        set_shares(&env, farmer.clone(), get_shares(&env, farmer) + shares);
+        set_pool_shares(&env, total_shares + locked + shares);
+
+        extend_instance_ttl(&env);
+
+        shares
+    }
+
+    fn withdraw(env: Env, farmer: Address, shares: i128) -> i128 {
+        farmer.require_auth();
+
+        if shares <= 0 {
+            panic_with_error!(&env, &Errors::WithdrawAmountTooLow);
+        }
+
+        let farmer_shares = get_shares(&env, farmer.clone());
+
+        if shares > farmer_shares {
+            panic_with_error!(&env, &Errors::SharesTooLow);
+        }
+
+        let asset = token::Client::new(&env, &get_pool_asset(&env));
+        let total_shares = get_pool_shares(&env);
+        let amount = shares.fixed_mul_floor(&env, &total_assets(&env), &total_shares);
+
+        // Stake that's currently planted can't be withdrawn until it's been harvested
+        if amount > asset.balance(&env.current_contract_address()) {
+            panic_with_error!(&env, &Errors::PoolIlliquid);
+        }
+
+        set_shares(&env, farmer.clone(), farmer_shares - shares);
+        set_pool_shares(&env, total_shares - shares);
+
+        if amount > 0 {
+            asset.transfer(&env.current_contract_address(), &farmer, &amount);
+        }
+
+        extend_instance_ttl(&env);
+
+        amount
+    }
+
+    fn shares(env: Env, farmer: Address) -> i128 {
+        get_shares(&env, farmer)
+    }
+
+    fn total_assets(env: Env) -> i128 {
+        total_assets(&env)
+    }
+
+    fn sync(env: Env, index: u32) -> i128 {
+        // No auth_require here, anyone can settle a pail the pool no longer holds
+
+        let planted = get_planted(&env, index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+
+        // Only once the farm no longer has the pail, e.g. it was harvested directly on the farm or its block was evicted
+        if FarmClient::new(&env, &get_pool_farm(&env))
+            .pending_pails(&env.current_contract_address(), &index, &1)
+            .first()
+            .is_some_and(|pail| pail.index == index)
+        {
+            panic_with_error!(&env, &Errors::PailPending);
+        }
+
+        settle(&env, index, planted)
+    }
+}
+
+#[contractimpl]
+impl OperatorTrait for Contract {
+    fn plant(env: Env, amount: i128) {
+        get_operator(&env).require_auth();
+
+        if amount < 0 {
+            panic_with_error!(&env, &Errors::PlantAmountTooLow);
+        }
+
+        let pool = env.current_contract_address();
+
+        // The farm burns the stake from the pool, which is a sub-invocation the pool must authorize itself
+        if amount > 0 {
+            env.authorize_as_current_contract(vec![
+                &env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: get_pool_asset(&env),
+                        fn_name: symbol_short!("burn"),
+                        args: (pool.clone(), amount).into_val(&env),
+                    },
+                    sub_invocations: vec![&env],
+                }),
+            ]);
+        }
+
+        let farm = FarmClient::new(&env, &get_pool_farm(&env));
+
+        farm.plant(&pool, &amount);
+
+        // `plant` doesn't return the block it planted into but it's the latest the pool has been active in
+        let index = farm.farmer_stats(&pool).last_active_index;
+
+        set_planted(&env, index, amount);
+        set_pool_planted(&env, get_pool_planted(&env) + amount);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn work(env: Env, hash: BytesN<32>, nonce: u64) -> u32 {
+        get_operator(&env).require_auth();
+
+        let gap = FarmClient::new(&env, &get_pool_farm(&env)).work(
+            &env.current_contract_address(),
+            &hash,
+            &nonce,
+        );
+
+        extend_instance_ttl(&env);
+
+        gap
+    }
+
+    fn harvest(env: Env, index: u32) -> i128 {
+        get_operator(&env).require_auth();
+
+        let planted = get_planted(&env, index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+
+        FarmClient::new(&env, &get_pool_farm(&env))
+            .harvest(&env.current_contract_address(), &index);
+
+        settle(&env, index, planted)
+    }
+}
+
+/// Stop counting the pail at `index` as planted and take the operator's fee from every reward the farm has paid the pool since the last settle
+fn settle(env: &Env, index: u32, planted: i128) -> i128 {
+    let operator = get_operator(env);
+    let pool = env.current_contract_address();
+
+    // The farm's running total also covers rewards from pails harvested directly on the farm
+    let rewards = FarmClient::new(env, &get_pool_farm(env))
+        .farmer_stats(&pool)
+        .total_reward;
+    let reward = rewards - get_pool_rewards(env);
+
+    set_pool_rewards(env, rewards);
+
+    // The stake is either back in the pool's balance or gone with an evicted block, either way it's no longer planted
+    remove_planted(env, index);
+    set_pool_planted(env, (get_pool_planted(env) - planted).max(0));
+
+    let fee = reward.max(0).fixed_mul_floor(
+        env,
+        &(get_operator_fee(env) as i128),
+        &(BASIS_POINTS as i128),
+    );
+
+    if fee > 0 {
+        token::Client::new(env, &get_pool_asset(env)).transfer(&pool, &operator, &fee);
+    }
+
+    extend_instance_ttl(env);
+
+    // The remaining reward stays in the pool, raising the value of every share pro-rata
+    reward - fee
+}
+
+fn total_assets(env: &Env) -> i128 {
+    token::Client::new(env, &get_pool_asset(env)).balance(&env.current_contract_address())
+        + get_pool_planted(env)
+}
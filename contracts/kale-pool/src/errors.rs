@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Errors {
+    PoolExists = 1,
+    PoolMissing = 2,
+    OperatorFeeTooHigh = 3,
+    DepositAmountTooLow = 4,
+    WithdrawAmountTooLow = 5,
+    SharesTooLow = 6,
+    PoolIlliquid = 7,
+    PlantAmountTooLow = 8,
+    PailMissing = 9,
+    PailPending = 10,
+}
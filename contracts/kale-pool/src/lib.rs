@@ -0,0 +1,53 @@
+#![no_std]
+
+use kale_types::{FarmerStats, PendingPail};
+use soroban_sdk::{contract, contractclient, Address, BytesN, Env, Vec};
+
+mod contract_pool;
+mod errors;
+mod storage;
+mod tests;
+mod types;
+
+pub const BASIS_POINTS: u32 = 10_000; // 100%
+pub const WEEK_OF_LEDGERS: u32 = 60 * 60 * 24 / 5 * 7; // assumes 5 second ledger close times
+pub const MINIMUM_SHARES: i128 = 1_000; // locked away by the first deposit so nobody can inflate the price of a share to round later deposits away
+
+#[contract]
+pub struct Contract;
+
+// The subset of the kale-sc `FarmTrait` the pool farms against
+#[contractclient(name = "FarmClient")]
+pub trait FarmInterface {
+    fn plant(env: Env, farmer: Address, amount: i128);
+
+    fn work(env: Env, farmer: Address, hash: BytesN<32>, nonce: u64) -> u32;
+
+    fn harvest(env: Env, farmer: Address, index: u32) -> i128;
+
+    fn farmer_stats(env: Env, farmer: Address) -> FarmerStats;
+
+    fn pending_pails(env: Env, farmer: Address, from: u32, limit: u32) -> Vec<PendingPail>;
+}
+
+pub trait PoolTrait {
+    fn __constructor(env: Env, operator: Address, farm: Address, asset: Address, fee: u32);
+
+    fn deposit(env: Env, farmer: Address, amount: i128) -> i128;
+
+    fn withdraw(env: Env, farmer: Address, shares: i128) -> i128;
+
+    fn shares(env: Env, farmer: Address) -> i128;
+
+    fn total_assets(env: Env) -> i128;
+
+    fn sync(env: Env, index: u32) -> i128;
+}
+
+pub trait OperatorTrait {
+    fn plant(env: Env, amount: i128);
+
+    fn work(env: Env, hash: BytesN<32>, nonce: u64) -> u32;
+
+    fn harvest(env: Env, index: u32) -> i128;
+}
@@ -0,0 +1,145 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{errors::Errors, types::Storage, WEEK_OF_LEDGERS};
+
+pub fn extend_instance_ttl(env: &Env) {
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .instance()
+        .extend_ttl(max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+
+pub fn has_operator(env: &Env) -> bool {
+    env.storage().instance().has::<Storage>(&Storage::Operator)
+}
+pub fn get_operator(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::Operator)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::PoolMissing))
+}
+pub fn set_operator(env: &Env, operator: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::Operator, operator);
+}
+
+pub fn get_operator_fee(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get::<Storage, u32>(&Storage::OperatorFee)
+        .unwrap_or(0)
+}
+pub fn set_operator_fee(env: &Env, fee: u32) {
+    env.storage()
+        .instance()
+        .set::<Storage, u32>(&Storage::OperatorFee, &fee);
+}
+
+pub fn get_pool_farm(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::PoolFarm)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::PoolMissing))
+}
+pub fn set_pool_farm(env: &Env, farm: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::PoolFarm, farm);
+}
+
+pub fn get_pool_asset(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<Storage, Address>(&Storage::PoolAsset)
+        .unwrap_or_else(|| panic_with_error!(&env, &Errors::PoolMissing))
+}
+pub fn set_pool_asset(env: &Env, asset: &Address) {
+    env.storage()
+        .instance()
+        .set::<Storage, Address>(&Storage::PoolAsset, asset);
+}
+
+pub fn get_pool_shares(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get::<Storage, i128>(&Storage::PoolShares)
+        .unwrap_or(0)
+}
+pub fn set_pool_shares(env: &Env, shares: i128) {
+    env.storage()
+        .instance()
+        .set::<Storage, i128>(&Storage::PoolShares, &shares);
+}
+
+pub fn get_pool_planted(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get::<Storage, i128>(&Storage::PoolPlanted)
+        .unwrap_or(0)
+}
+pub fn set_pool_planted(env: &Env, planted: i128) {
+    env.storage()
+        .instance()
+        .set::<Storage, i128>(&Storage::PoolPlanted, &planted);
+}
+
+pub fn get_pool_rewards(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get::<Storage, i128>(&Storage::PoolRewards)
+        .unwrap_or(0)
+}
+pub fn set_pool_rewards(env: &Env, rewards: i128) {
+    env.storage()
+        .instance()
+        .set::<Storage, i128>(&Storage::PoolRewards, &rewards);
+}
+
+pub fn get_planted(env: &Env, index: u32) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<Storage, i128>(&Storage::Planted(index))
+}
+pub fn set_planted(env: &Env, index: u32, planted: i128) {
+    let planted_key = Storage::Planted(index);
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .persistent()
+        .set::<Storage, i128>(&planted_key, &planted);
+    env.storage()
+        .persistent()
+        .extend_ttl(&planted_key, max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+pub fn remove_planted(env: &Env, index: u32) {
+    env.storage()
+        .persistent()
+        .remove::<Storage>(&Storage::Planted(index));
+}
+
+pub fn get_shares(env: &Env, farmer: Address) -> i128 {
+    let shares_key = Storage::Shares(farmer);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, i128>(&shares_key)
+        .unwrap_or(0)
+}
+pub fn set_shares(env: &Env, farmer: Address, shares: i128) {
+    let shares_key = Storage::Shares(farmer);
+
+    if shares == 0 {
+        env.storage().persistent().remove::<Storage>(&shares_key);
+    } else {
+        let max_ttl = env.storage().max_ttl();
+
+        env.storage()
+            .persistent()
+            .set::<Storage, i128>(&shares_key, &shares);
+        env.storage()
+            .persistent()
+            .extend_ttl(&shares_key, max_ttl - WEEK_OF_LEDGERS, max_ttl);
+    }
+}
@@ -0,0 +1,340 @@
+extern crate std;
+
+use crate::{errors::Errors, Contract, ContractClient, MINIMUM_SHARES};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Ledger, MockAuth, MockAuthInvoke},
+    token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal,
+};
+
+fn generate_hash(
+    env: &Env,
+    index: &u32,
+    nonce: &u64,
+    entropy: &BytesN<32>,
+    farmer: &Address,
+) -> BytesN<32> {
+    let mut hash_array = [0u8; 76];
+
+    let mut farmer_array = [0u8; 32];
+    let farmer_bytes = farmer.clone().to_xdr(env);
+    farmer_bytes
+        .slice(farmer_bytes.len() - 32..)
+        .copy_into_slice(&mut farmer_array);
+
+    hash_array[..4].copy_from_slice(&index.to_be_bytes());
+    hash_array[4..12].copy_from_slice(&nonce.to_be_bytes());
+    hash_array[12..44].copy_from_slice(&entropy.to_array());
+    hash_array[44..].copy_from_slice(&farmer_array);
+
+    env.crypto()
+        .keccak256(&Bytes::from_array(env, &hash_array))
+        .to_bytes()
+}
+
+#[test]
+fn test() {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.ledger().set_min_temp_entry_ttl(17280);
+    env.ledger().set_min_persistent_entry_ttl(2073600);
+
+    env.mock_all_auths();
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    let homesteader: Address = Address::generate(&env);
+    let operator: Address = Address::generate(&env);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(homesteader.clone());
+    let asset_address = asset_sac.address();
+    let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
+    let asset_client = token::Client::new(&env, &asset_address);
+
+    let farmer_1: Address = Address::generate(&env);
+    let farmer_2: Address = Address::generate(&env);
+
+    let amount_1 = 100_0000000;
+    let amount_2 = 300_0000000;
+
+    asset_homesteader.mint(&farmer_1, &amount_1);
+    asset_homesteader.mint(&farmer_2, &amount_2);
+
+    let farm_address: Address = env.register(
+        kale_sc::Contract,
+        (
+            &homesteader,
+            &asset_address,
+            None::<kale_sc::types::FarmConfig>,
+        ),
+    );
+    let farm_client = kale_sc::ContractClient::new(&env, &farm_address);
+
+    asset_homesteader.set_admin(&farm_address);
+
+    // 10% operator fee
+    let pool_address: Address = env.register(
+        Contract,
+        (&operator, &farm_address, &asset_address, &1_000u32),
+    );
+    let pool_client = ContractClient::new(&env, &pool_address);
+
+    // The first deposit locks away the pool's minimum shares
+    assert_eq!(
+        pool_client.deposit(&farmer_1, &amount_1),
+        amount_1 - MINIMUM_SHARES
+    );
+    assert_eq!(pool_client.deposit(&farmer_2, &amount_2), amount_2);
+
+    let stake = 200_0000000;
+
+    // Only the operator signs, the pool authorizes the farm's `burn` of its stake itself
+    pool_client
+        .mock_auths(&[MockAuth {
+            address: &operator,
+            invoke: &MockAuthInvoke {
+                contract: &pool_address,
+                fn_name: "plant",
+                args: (stake,).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .plant(&stake);
+
+    env.mock_all_auths();
+
+    // Planted stake still counts towards the pool but can't be withdrawn
+    assert_eq!(pool_client.total_assets(), amount_1 + amount_2);
+    assert_eq!(
+        asset_client.balance(&pool_address),
+        amount_1 + amount_2 - stake
+    );
+
+    let err = pool_client
+        .try_withdraw(&farmer_2, &amount_2)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::PoolIlliquid.into());
+
+    env.ledger().set_sequence_number(sequence + 10);
+
+    // Genesis block, index 0 with empty entropy
+    let nonce = 0;
+    let hash = generate_hash(
+        &env,
+        &0,
+        &nonce,
+        &BytesN::from_array(&env, &[0; 32]),
+        &pool_address,
+    );
+
+    assert_eq!(pool_client.work(&hash, &nonce), 10);
+
+    env.ledger()
+        .set_timestamp(timestamp + kale_sc::BLOCK_INTERVAL);
+
+    farm_client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + kale_sc::GRACE_PERIOD);
+
+    let reward = pool_client.harvest(&0);
+    let fee = asset_client.balance(&operator);
+
+    // As the only farmer in the block the pool takes the whole block reward
+    assert_eq!(reward + fee, kale_sc::BLOCK_REWARD);
+    assert_eq!(fee, kale_sc::BLOCK_REWARD / 10);
+    assert_eq!(pool_client.total_assets(), amount_1 + amount_2 + reward);
+    assert_eq!(
+        asset_client.balance(&pool_address),
+        amount_1 + amount_2 + reward
+    );
+
+    // Rewards are distributed pro-rata to each farmer's shares
+    let total_assets = amount_1 + amount_2 + reward;
+    let total_shares = amount_1 + amount_2;
+    let withdrawn_1 = pool_client.withdraw(&farmer_1, &(amount_1 - MINIMUM_SHARES));
+
+    assert_eq!(
+        withdrawn_1,
+        (amount_1 - MINIMUM_SHARES) * total_assets / total_shares
+    );
+
+    let withdrawn_2 = pool_client.withdraw(&farmer_2, &amount_2);
+
+    assert_eq!(
+        withdrawn_2,
+        amount_2 * (total_assets - withdrawn_1) / (amount_2 + MINIMUM_SHARES)
+    );
+    assert_eq!(pool_client.shares(&farmer_1), 0);
+    assert_eq!(pool_client.shares(&farmer_2), 0);
+}
+
+#[test]
+fn test_sync() {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.ledger().set_min_temp_entry_ttl(17280);
+    env.ledger().set_min_persistent_entry_ttl(2073600);
+
+    env.mock_all_auths();
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    let homesteader: Address = Address::generate(&env);
+    let operator: Address = Address::generate(&env);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(homesteader.clone());
+    let asset_address = asset_sac.address();
+    let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
+    let asset_client = token::Client::new(&env, &asset_address);
+
+    let farmer: Address = Address::generate(&env);
+    let amount = 100_0000000;
+
+    asset_homesteader.mint(&farmer, &amount);
+
+    let farm_address: Address = env.register(
+        kale_sc::Contract,
+        (
+            &homesteader,
+            &asset_address,
+            None::<kale_sc::types::FarmConfig>,
+        ),
+    );
+    let farm_client = kale_sc::ContractClient::new(&env, &farm_address);
+
+    asset_homesteader.set_admin(&farm_address);
+
+    // 10% operator fee
+    let pool_address: Address = env.register(
+        Contract,
+        (&operator, &farm_address, &asset_address, &1_000u32),
+    );
+    let pool_client = ContractClient::new(&env, &pool_address);
+
+    pool_client.deposit(&farmer, &amount);
+    pool_client.plant(&40_0000000);
+
+    // The farm still holds the pail
+    let err = pool_client.try_sync(&0).unwrap_err().unwrap();
+
+    assert_eq!(err, Errors::PailPending.into());
+
+    env.ledger().set_sequence_number(sequence + 10);
+
+    let nonce = 0;
+    let hash = generate_hash(
+        &env,
+        &0,
+        &nonce,
+        &BytesN::from_array(&env, &[0; 32]),
+        &pool_address,
+    );
+
+    pool_client.work(&hash, &nonce);
+
+    env.ledger()
+        .set_timestamp(timestamp + kale_sc::BLOCK_INTERVAL);
+
+    pool_client.plant(&20_0000000);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + kale_sc::GRACE_PERIOD);
+
+    // Anyone can harvest the pool's pail straight from the farm
+    let reward = farm_client.harvest(&pool_address, &0);
+
+    assert!(pool_client.try_harvest(&0).is_err());
+
+    // so it's settled with `sync`, which still takes the operator's fee and stops counting the stake as planted
+    let fee = reward / 10;
+
+    assert_eq!(pool_client.sync(&0), reward - fee);
+    assert_eq!(asset_client.balance(&operator), fee);
+    assert_eq!(pool_client.total_assets(), amount + reward - fee);
+
+    let err = pool_client.try_sync(&0).unwrap_err().unwrap();
+
+    assert_eq!(err, Errors::PailMissing.into());
+
+    // The second pail is never worked and its block is evicted, its stake is socialized across all shares
+    env.as_contract(&farm_address, || {
+        env.storage()
+            .temporary()
+            .remove(&kale_sc::types::Storage::PailV2(pool_address.clone(), 1));
+    });
+
+    assert_eq!(pool_client.sync(&1), 0);
+    assert_eq!(
+        pool_client.total_assets(),
+        amount + reward - fee - 20_0000000
+    );
+    assert_eq!(
+        asset_client.balance(&pool_address),
+        pool_client.total_assets()
+    );
+}
+
+#[test]
+fn test_first_deposit() {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.mock_all_auths();
+
+    let homesteader: Address = Address::generate(&env);
+    let operator: Address = Address::generate(&env);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(homesteader.clone());
+    let asset_address = asset_sac.address();
+    let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
+    let asset_client = token::Client::new(&env, &asset_address);
+
+    let farm_address: Address = Address::generate(&env);
+    let pool_address: Address =
+        env.register(Contract, (&operator, &farm_address, &asset_address, &0u32));
+    let pool_client = ContractClient::new(&env, &pool_address);
+
+    let attacker: Address = Address::generate(&env);
+    let farmer: Address = Address::generate(&env);
+    let amount = 100_0000000;
+
+    asset_homesteader.mint(&attacker, &(MINIMUM_SHARES + 1 + amount));
+    asset_homesteader.mint(&farmer, &amount);
+
+    // Too little to cover the locked shares
+    let err = pool_client
+        .try_deposit(&attacker, &MINIMUM_SHARES)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::DepositAmountTooLow.into());
+
+    // A single share followed by a donation as large as the next deposit
+    assert_eq!(pool_client.deposit(&attacker, &(MINIMUM_SHARES + 1)), 1);
+
+    asset_client.transfer(&attacker, &pool_address, &amount);
+
+    // mostly gifts the donation to the locked shares rather than rounding the farmer's deposit away
+    let shares = pool_client.deposit(&farmer, &amount);
+
+    assert!(shares >= MINIMUM_SHARES - 1);
+    assert!(pool_client.withdraw(&farmer, &shares) > amount * 99 / 100);
+    assert!(pool_client.withdraw(&attacker, &1) < amount / 100);
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Storage {
+    Operator,        // : address
+    OperatorFee,     // : u32
+    PoolFarm,        // : address
+    PoolAsset,       // : address
+    PoolShares,      // : i128
+    PoolPlanted,     // : i128
+    PoolRewards,     // : i128 (the farm's `total_reward` for the pool when it was last settled)
+    Planted(u32),    // (index) : i128
+    Shares(Address), // (farmer) : i128
+}
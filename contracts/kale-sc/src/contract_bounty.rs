@@ -0,0 +1,163 @@
+use crate::ContractArgs;
+use soroban_sdk::{contractimpl, panic_with_error, token, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+use crate::{
+    contract_farm::{count_leading_zeros, generate_hash, work_pail},
+    errors::Errors,
+    storage::{
+        extend_instance_ttl, get_block, get_bounty, get_bounty_commit, get_farm_asset,
+        get_farm_index, get_farm_paused, get_pail, has_bounty, has_pail, remove_bounty,
+        remove_bounty_commit, set_bounty, set_bounty_commit,
+    },
+    types::{Bounty, BountyCommit},
+    BountyTrait, Contract, ContractClient,
+};
+
+#[contractimpl]
+impl BountyTrait for Contract {
+    fn post_bounty(env: Env, farmer: Address, index: u32, amount: i128, zeros: u32) {
+        farmer.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, &Errors::BountyAmountTooLow);
+        }
+
+        if get_farm_paused(&env) {
+            panic_with_error!(&env, &Errors::FarmPaused);
+        }
+
+        // Bounties can only be posted against the block currently being worked
+        if index != get_farm_index(&env) {
+            panic_with_error!(&env, &Errors::BlockInvalid);
+        }
+
+        if !has_pail(&env, farmer.clone(), index) {
+            panic_with_error!(&env, &Errors::PailMissing);
+        }
+
+        if has_bounty(&env, farmer.clone(), index) {
+            panic_with_error!(&env, &Errors::BountyExists);
+        }
+
+        // Escrow the bounty the same way we escrow stake, by burning it now and minting it back out later
+        token::Client::new(&env, &get_farm_asset(&env)).burn(&farmer, &amount);
+
+        // Persistent so the escrow outlives its block, `cancel_bounty` can only run once the block has closed
+        set_bounty(&env, farmer, index, Bounty { amount, zeros });
+
+        extend_instance_ttl(&env);
+    }
+
+    fn commit_bounty(env: Env, worker: Address, farmer: Address, commitment: BytesN<32>) {
+        worker.require_auth();
+
+        let index = get_farm_index(&env);
+
+        if !has_bounty(&env, farmer.clone(), index) {
+            panic_with_error!(&env, &Errors::BountyMissing);
+        }
+
+        // Recommitting only replaces this worker's own commitment
+        set_bounty_commit(
+            &env,
+            worker,
+            farmer,
+            index,
+            BountyCommit {
+                commitment,
+                sequence: env.ledger().sequence(),
+            },
+        );
+
+        extend_instance_ttl(&env);
+    }
+
+    fn work_bounty(
+        env: Env,
+        worker: Address,
+        farmer: Address,
+        hash: BytesN<32>,
+        nonce: u64,
+    ) -> i128 {
+        worker.require_auth();
+
+        let index = get_farm_index(&env);
+        let Bounty { amount, zeros } = get_bounty(&env, farmer.clone(), index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BountyMissing));
+        let BountyCommit {
+            commitment,
+            sequence,
+        } = get_bounty_commit(&env, worker.clone(), farmer.clone(), index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BountyCommitMissing));
+
+        // The hash was committed to before anyone could see it, so only this worker can have found it
+        // A commitment from this same ledger could have been made after seeing the hash in another transaction
+        if sequence >= env.ledger().sequence() {
+            panic_with_error!(&env, &Errors::BountyCommitMissing);
+        }
+
+        let mut committed = Bytes::new(&env);
+
+        committed.append(&worker.clone().to_xdr(&env));
+        committed.append(&hash.clone().into());
+
+        if env.crypto().keccak256(&committed).to_bytes() != commitment {
+            panic_with_error!(&env, &Errors::HashInvalid);
+        }
+
+        let hash_zeros = count_leading_zeros(&hash);
+        let pail = get_pail(&env, farmer.clone(), index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+
+        if pail
+            .zeros
+            .is_some_and(|pail_zeros| pail_zeros >= hash_zeros)
+        {
+            // Already worked with at least as good a hash, e.g. this one replayed through `work` once it was revealed
+            // so just check it's a hash for the farmer's pail rather than working it again
+            let block = get_block(&env, index)
+                .unwrap_or_else(|| panic_with_error!(&env, &Errors::BlockMissing));
+
+            if hash != generate_hash(&env, &index, &nonce, &block.entropy, &farmer) {
+                panic_with_error!(&env, &Errors::HashInvalid);
+            }
+        } else {
+            // `generate_hash` binds the `farmer`, so the worker can only ever improve the farmer's pail
+            // No grace period here, a late hash would leave the bounty's pail untouched
+            work_pail(&env, &farmer, hash, nonce, false);
+        }
+
+        if hash_zeros < zeros {
+            panic_with_error!(&env, &Errors::ZeroCountTooLow);
+        }
+
+        token::StellarAssetClient::new(&env, &get_farm_asset(&env)).mint(&worker, &amount);
+
+        remove_bounty(&env, farmer.clone(), index);
+        remove_bounty_commit(&env, worker, farmer, index);
+
+        extend_instance_ttl(&env);
+
+        amount
+    }
+
+    fn cancel_bounty(env: Env, farmer: Address, index: u32) -> i128 {
+        farmer.require_auth();
+
+        let Bounty { amount, .. } = get_bounty(&env, farmer.clone(), index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BountyMissing));
+
+        // Workers must be able to trust an open bounty, so it can only be reclaimed once its block has closed
+        if index >= get_farm_index(&env) {
+            panic_with_error!(&env, &Errors::BountyNotExpired);
+        }
+
+        token::StellarAssetClient::new(&env, &get_farm_asset(&env)).mint(&farmer, &amount);
+
+        remove_bounty(&env, farmer, index);
+
+        extend_instance_ttl(&env);
+
+        amount
+    }
+}
@@ -1,16 +1,29 @@
-use crate::{ContractArgs, BLOCKS_PER_MONTH, BLOCK_SCALE, INVERSE_DECAY_RATE, V2_GENESIS_BLOCK};
+use crate::{
+    types::FarmConfig, ContractArgs, BASIS_POINTS, BLOCK_SCALE, BLOCK_SHARDS, DECAY_STEPS,
+    INTERVAL_STEP, LEDGER_SECONDS, STAKE_BUCKETS, ZEROS_BUCKETS,
+};
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{contractimpl, panic_with_error, token, xdr::ToXdr, Address, Bytes, BytesN, Env};
+use soroban_sdk::{
+    contractimpl, panic_with_error, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    Vec,
+};
 
 use crate::{
     errors::Errors,
+    math::{log2, pow16, pow_scaled, sqrt},
     storage::{
-        bump_farm_index, extend_instance_ttl, get_block, get_farm_asset, get_farm_block,
-        get_farm_index, get_farm_paused, get_pail, has_pail, remove_pail, set_block,
-        set_farm_block, set_pail,
+        bump_farm_index, extend_instance_ttl, get_block, get_claimed, get_farm_asset,
+        get_farm_block, get_farm_carry, get_farm_config, get_farm_index, get_farm_paused,
+        get_farm_rollover, get_farm_treasury, get_farmer_pails, get_farmer_stats, get_pail,
+        get_shard, get_team_pail, get_unclaimed, has_pail, has_team_pail, remove_claimed,
+        remove_farm_block, remove_pail, remove_team_pail, remove_unclaimed, set_block,
+        set_block_summary, set_claimed, set_farm_carry, set_farm_legacy, set_farm_rollover,
+        set_farmer_pails, set_farmer_stats, set_pail, set_shard, set_unclaimed,
     },
-    types::{Block, Pail},
-    Contract, ContractClient, FarmTrait, BLOCK_INTERVAL, BLOCK_REWARD,
+    types::{
+        Block, BlockSummary, Curve, Era, FarmerStats, Pail, Schedule, Shard, StakeCap, Treasury,
+    },
+    Contract, ContractClient, FarmTrait,
 };
 
 #[contractimpl]
@@ -18,228 +31,596 @@ impl FarmTrait for Contract {
     fn plant(env: Env, farmer: Address, amount: i128) {
         farmer.require_auth();
 
-        if amount < 0 {
-            panic_with_error!(&env, &Errors::PlantAmountTooLow);
+        plant_pail(&env, &farmer, &farmer, amount, false);
+    }
+
+    fn work(env: Env, farmer: Address, hash: BytesN<32>, nonce: u64) -> u32 {
+        // No auth_require here so others can call this function on the `farmer`'s behalf
+
+        work_pail(&env, &farmer, hash, nonce, true)
+    }
+
+    fn harvest(env: Env, farmer: Address, index: u32) -> i128 {
+        let asset = get_farm_asset(&env);
+        let farm_index = get_farm_index(&env);
+        let block = get_block(&env, index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BlockMissing));
+        let Pail {
+            gap,
+            stake,
+            zeros,
+            streak,
+            ticket,
+            ..
+        } = get_pail(&env, farmer.clone(), index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+
+        if !is_harvestable(&env, farm_index, index) {
+            panic_with_error!(&env, &Errors::HarvestNotReady);
         }
 
-        if get_farm_paused(&env) {
-            panic_with_error!(&env, &Errors::FarmPaused);
+        if gap.is_none() || zeros.is_none() {
+            panic_with_error!(&env, &Errors::WorkMissing);
         }
 
-        let asset = get_farm_asset(&env);
-        let mut index = get_farm_index(&env);
-        let mut farm_block = get_farm_block(&env)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::FarmBlockMissing));
-
-        // NOTE originally we were calling `new_block` as the branch logic which read Block+0
-        // but then in race txns 99+ we needed to read Block+1 but didn't have the read_bytes for that
-        // If we include Block+0 as the branch logic it's LedgerKey would be included in the RW footprint which will consume read_bytes
-        // Then in the 99+ when we try to read an additional Block+1 it will fail because we don't have the read_bytes for it
-        // TODO Originally I noticed a variance of 460 and 240 read_bytes in the 99+ which I don't understand. 460 makes sense but 240 doesn't
-        // 240 is the length of the Pail LedgerEntry so actually what was happening wasn't the Block it was the Pail read somehow?
-        // I _think_ the only way 240 would make any sense as a deficit would be in the case of duplicate `plant` calls for the same farmer
-
-        // if the block is >= BLOCK_INTERVAL old, we need to create a new one
-        let mut block = if env.ledger().timestamp() >= farm_block.timestamp + BLOCK_INTERVAL {
-            let block = new_block(&env, &farm_block);
-
-            // call `get_block` on the previous block so we've got the necessary read_bytes for the N+ transactions which would otherwise be a `Block` short
-            // e.g. 100 tx simulate this branch but only 1 actually executes it and the rest go to the else branch
-            // this will give us a bonus budget of 460 read_bytes which the 99+ transactions can use to read the block this simulation didn't need to read
-            // get_block(&env, index);
-            // TODO turns out we don't need this. It just adds a footprint item we don't actually need
-
-            // ensure we put this after the `new_block` above
-            farm_block = new_farm_block(&env);
-            bump_farm_index(&env, &mut index);
-
-            block
-        } else {
-            match get_block(&env, index) {
-                // genesis or evicted
-                None => {
-                    if index > 0 {
-                        // Only when we're in an evicted scenario should the index be bumped
-                        bump_farm_index(&env, &mut index);
-                    }
+        let gap = gap.unwrap();
+        let zeros = zeros.unwrap();
+
+        let (normalized_gap, normalized_stake, normalized_zeros, normalized_streak) =
+            generate_normalizations(&env, &block, gap, stake, zeros, streak);
+
+        // The decayed block reward was resolved when the block opened so a `set_config` since can't change it
+        let block_reward = block.block_reward;
+        let prize = block_reward.fixed_mul_floor(
+            &env,
+            &(block.lottery_share as i128),
+            &(BASIS_POINTS as i128),
+        );
+        let jackpot = block_reward.fixed_mul_floor(
+            &env,
+            &(block.jackpot_share as i128),
+            &(BASIS_POINTS as i128),
+        );
+
+        let mut reward = (normalized_gap + normalized_stake + normalized_zeros + normalized_streak)
+            .fixed_mul_floor(
+                &env,
+                &(block_reward - prize - jackpot + block.carry + block.staked_total),
+                &block.normalized_total.max(1),
+            );
+
+        let shard = get_shard_index(&env, &farmer);
+
+        // The winning ticket takes the lottery prize on top of its share
+        if ticket == Some(block.lottery_ticket) && shard == block.lottery_shard {
+            reward += prize;
+
+            env.events()
+                .publish((symbol_short!("lottery"), farmer.clone(), index), prize);
+        }
+
+        // and the best hash takes the jackpot
+        if ticket == Some(block.best_ticket) && shard == block.best_shard && jackpot > 0 {
+            reward += jackpot;
 
-                    new_block(&env, &farm_block)
+            env.events()
+                .publish((symbol_short!("jackpot"), farmer.clone(), index), jackpot);
+        }
+
+        // The block has closed so its totals are settled and stay read only
+        // Harvests are counted in the farmer's shard so concurrent harvests in different shards touch disjoint keys
+        let mut farm_shard = get_shard(&env, index, shard).unwrap_or_else(|| new_shard(&env));
+
+        farm_shard.harvested_count += 1;
+        farm_shard.total_reward_minted += reward;
+
+        set_shard(&env, index, shard, &farm_shard);
+        set_claimed(
+            &env,
+            index,
+            shard,
+            get_claimed(&env, index, shard).unwrap_or(0) + reward,
+        );
+
+        // Carve the treasury's cut out of the reward only, the returned stake is always the farmer's
+        let fee = match get_farm_treasury(&env) {
+            Some(Treasury { address, fee }) if fee > 0 && reward > 0 => {
+                let fee = reward.fixed_mul_floor(&env, &(fee as i128), &(BASIS_POINTS as i128));
+
+                if fee > 0 {
+                    token::StellarAssetClient::new(&env, &asset).mint(&address, &fee);
+
+                    env.events()
+                        .publish((symbol_short!("treasury"), farmer.clone(), index), fee);
                 }
-                Some(block) => block,
+
+                fee
             }
+            _ => 0,
         };
 
-        // must come after block discovery as the index may have been bumped
-        if has_pail(&env, farmer.clone(), index) {
-            panic_with_error!(&env, &Errors::PailExists);
+        let reward = reward - fee;
+
+        match get_team_pail(&env, farmer.clone(), index) {
+            // Team pails split the reward by each member's share of the team's stake and return each member's own stake
+            Some(stakes) => {
+                for (member, member_stake) in stakes.iter() {
+                    let member_reward = if stake > 0 {
+                        reward.fixed_mul_floor(&env, &member_stake, &stake)
+                    } else {
+                        reward / stakes.len() as i128
+                    };
+                    let reward_and_stake = member_reward + member_stake;
+
+                    if reward_and_stake > 0 {
+                        token::StellarAssetClient::new(&env, &asset)
+                            .mint(&member, &reward_and_stake);
+                    }
+                }
+
+                remove_team_pail(&env, farmer.clone(), index);
+            }
+            None => {
+                let reward_and_stake = reward + stake;
+
+                if reward_and_stake > 0 {
+                    token::StellarAssetClient::new(&env, &asset).mint(&farmer, &reward_and_stake);
+                }
+            }
         }
 
-        block.staked_total += amount;
+        remove_pail(&env, farmer.clone(), index);
+
+        let mut indexes = get_farmer_pails(&env, farmer.clone());
 
-        if amount > 0 {
-            token::Client::new(&env, &asset).burn(&farmer, &amount);
+        if let Some(i) = indexes.first_index_of(index) {
+            indexes.remove(i);
+            set_farmer_pails(&env, farmer.clone(), &indexes);
         }
 
-        if amount > farm_block.max_stake {
-            farm_block.max_stake = amount;
+        let mut stats = get_farmer_stats(&env, farmer.clone());
+
+        stats.blocks_harvested += 1;
+        stats.total_reward += reward;
+
+        set_farmer_stats(&env, farmer, &stats);
+
+        extend_instance_ttl(&env);
+
+        reward
+    }
+
+    fn sweep_expired(env: Env, index: u32) -> i128 {
+        // No auth_require here, anyone can recycle what can no longer be harvested
+
+        let mut unclaimed = get_unclaimed(&env, index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::UnclaimedMissing));
+
+        // Harvesting needs the block so once it's been evicted nothing left in it can be claimed
+        if get_block(&env, index).is_some() {
+            panic_with_error!(&env, &Errors::BlockNotExpired);
         }
 
-        if amount < farm_block.min_stake {
-            farm_block.min_stake = amount;
+        remove_unclaimed(&env, index);
+
+        // What's left is the pool less every shard's harvests, rounding dust included
+        for shard in 0..BLOCK_SHARDS {
+            if let Some(claimed) = get_claimed(&env, index, shard) {
+                unclaimed -= claimed;
+
+                remove_claimed(&env, index, shard);
+            }
         }
 
-        let pail = Pail {
-            sequence: env.ledger().sequence(),
-            gap: None,
-            stake: amount,
-            zeros: None,
-        };
+        let unclaimed = unclaimed.max(0);
+
+        if unclaimed > 0 {
+            set_farm_carry(&env, get_farm_carry(&env) + unclaimed);
+        }
 
-        set_pail(&env, farmer, index, pail);
-        set_block(&env, index, &block);
-        set_farm_block(&env, &farm_block);
+        env.events()
+            .publish((symbol_short!("sweep"), index), unclaimed);
 
         extend_instance_ttl(&env);
+
+        unclaimed
     }
+}
 
-    fn work(env: Env, farmer: Address, hash: BytesN<32>, nonce: u64) -> u32 {
-        // No auth_require here so others can call this function on the `farmer`'s behalf
+/// Plant `amount` from the `staker` into the `farmer`'s pail for the current block, returning the block index
+///
+/// Solo pails are planted exactly once. Team pails (`team`) are topped up by each member until they've been worked.
+pub fn plant_pail(env: &Env, farmer: &Address, staker: &Address, amount: i128, team: bool) -> u32 {
+    if amount < 0 {
+        panic_with_error!(env, &Errors::PlantAmountTooLow);
+    }
 
-        let index = get_farm_index(&env);
-        let mut farm_block = get_farm_block(&env)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::FarmBlockMissing));
-        let mut block = get_block(&env, index)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BlockMissing));
-        let mut pail = get_pail(&env, farmer.clone(), index)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+    if get_farm_paused(env) {
+        panic_with_error!(env, &Errors::FarmPaused);
+    }
+
+    let asset = get_farm_asset(env);
+    let mut index = get_farm_index(env);
+
+    // NOTE originally we were calling `new_block` as the branch logic which read Block+0
+    // but then in race txns 99+ we needed to read Block+1 but didn't have the read_bytes for that
+    // e.g. 100 tx simulate the rollover branch but only 1 actually executes it and the rest go to the steady branch
+    // The 460 read_bytes deficit was Block+1 and the 240 was the first entry the steady branch found that the rollover branch had only ever written
+    // Both only existed by the time the 99+ executed, so their simulations never budgeted for reading them
+
+    // NOTE every key the steady branch touches is also touched by the rollover branch of the previous block and already exists, at the same fixed size, when that rollover is simulated
+    // Each rollover pre-creates a placeholder Block+1 and all of its shards so the 99+ read exactly what their simulation read
+    // The block is only ever written when it's created, every other `plant` and `work` writes to the farmer's shard instead
+    // so concurrent farmers in different shards touch disjoint keys
+    let stake_cap = match get_block(env, index) {
+        // if the block is < its interval old, keep planting into it
+        Some(block) if !is_block_closed(env, &block) => block.stake_cap,
+        // otherwise close it out and create the next one
+        Some(mut block) => {
+            let farm_block = fold_shards(env, index, &mut block);
+
+            set_block(env, index, &block);
+            set_block_summary(env, index, &new_block_summary(index, &block));
+
+            // Record the block's pool so whatever's never harvested can be swept once it's evicted
+            set_unclaimed(
+                env,
+                index,
+                block.block_reward + block.carry + block.staked_total,
+            );
+
+            // The block before this one is past its grace period, if nobody worked it nobody ever will
+            if index > 0 {
+                if let Some(previous) = get_block(env, index - 1) {
+                    if previous.worked_count == 0 {
+                        carry_remainder(env, index - 1, &previous);
+                    }
+                }
+            }
 
-        let generated_hash = generate_hash(&env, &index, &nonce, &block.entropy, &farmer);
-        let sequence = env.ledger().sequence();
-        let gap = sequence - pail.sequence;
-        let mut zeros = 0;
+            // before the rollover sequence moves on, the next interval depends on how long this block was open
+            let next_block = new_block(env, index + 1, &farm_block);
 
-        // Ensure there's at least 1 ledger gap between plant and work (sorry RowBear, you're a genius)
-        if gap == 0 {
-            panic_with_error!(env, &Errors::GapCountTooLow);
-        }
+            // ensure we put this after the `fold_shards` above
+            bump_farm_index(env, &mut index);
+            set_farm_rollover(env, env.ledger().sequence());
+
+            let block = next_block;
 
-        // TODO No real reason to check if the hash is valid. If it's not the zero count would just be low or nil which is fine
-        if hash != generated_hash {
-            panic_with_error!(&env, &Errors::HashInvalid);
+            set_block(env, index, &block);
+            set_placeholders(env, index + 1);
+
+            block.stake_cap
         }
+        // genesis or evicted
+        None => {
+            let farm_block = if index > 0 {
+                // Carry forward whatever shards of the evicted block are still around
+                let farm_block = fold_shards(env, index, &mut new_farm_block(env));
+
+                // Only when we're in an evicted scenario should the index be bumped
+                bump_farm_index(env, &mut index);
 
-        for byte in hash {
-            if byte == 0 {
-                zeros += 2;
+                farm_block
             } else {
-                zeros += byte.leading_zeros() / 4;
-                break;
+                new_farm_block(env)
+            };
+
+            let block = new_block(env, index, &farm_block);
+
+            set_farm_rollover(env, env.ledger().sequence());
+
+            // There was no previous rollover to prepare this block's shards
+            set_placeholders(env, index);
+            set_block(env, index, &block);
+            set_placeholders(env, index + 1);
+
+            block.stake_cap
+        }
+    };
+
+    let mut stats = get_farmer_stats(env, farmer.clone());
+
+    // must come after block discovery as the index may have been bumped
+    let (mut pail, planted) = match get_pail(env, farmer.clone(), index) {
+        None => (
+            Pail {
+                sequence: env.ledger().sequence(),
+                gap: None,
+                stake: 0,
+                zeros: None,
+                streak: calculate_streak_bonus(env, &stats, index),
+                ticket: None,
+            },
+            true,
+        ),
+        // A team can only top up its own team pail, not a solo pail planted by the team address
+        Some(pail) if team && has_team_pail(env, farmer.clone(), index) => {
+            // Once a team pail has been worked its stake has been reclaimed and normalized so it can't grow
+            if pail.zeros.is_some() {
+                panic_with_error!(env, &Errors::PailWorked);
             }
+
+            (pail, false)
         }
+        Some(_) => panic_with_error!(env, &Errors::PailExists),
+    };
+
+    // Team top-ups count towards the same cap as a solo pail
+    if pail.stake + amount > stake_cap {
+        panic_with_error!(env, &Errors::PlantAmountTooHigh);
+    }
 
-        // TODO save per farmer normalizations to their Pail so we don't have to recalculate during harvest
-        // Would allow us to upgrade the normalizations logic without needing to toss the active block
+    let shard = get_shard_index(env, farmer);
+    let mut farm_shard = get_shard(env, index, shard).unwrap_or_else(|| new_shard(env));
 
-        let (normalized_gap, normalized_stake, normalized_zeros) =
-            generate_normalizations(&env, &block, gap, pail.stake, zeros);
+    farm_shard.staked_total += amount;
+    farm_shard.planted_total += amount;
 
-        block.normalized_total += normalized_gap + normalized_stake + normalized_zeros;
+    if planted {
+        farm_shard.planted_count += 1;
+    }
 
-        match pail.zeros {
-            Some(prev_zeros) => {
-                if zeros <= prev_zeros {
-                    panic_with_error!(&env, &Errors::ZeroCountTooLow);
-                }
+    if amount > 0 {
+        token::Client::new(env, &asset).burn(staker, &amount);
+    }
+
+    // A team top-up moves its pail up the tally rather than counting it twice
+    move_tally(
+        &mut farm_shard.stake_tally,
+        (!planted).then(|| stake_bucket(pail.stake)),
+        stake_bucket(pail.stake + amount),
+    );
+
+    pail.stake += amount;
+
+    if pail.stake > farm_shard.max_stake {
+        farm_shard.max_stake = pail.stake;
+    }
+
+    if pail.stake < farm_shard.min_stake {
+        farm_shard.min_stake = pail.stake;
+    }
 
-                let (prev_normalized_gap, prev_normalized_stake, prev_normalized_zeros) =
-                    generate_normalizations(&env, &block, gap, pail.stake, prev_zeros);
+    set_pail(env, farmer.clone(), index, pail);
+    set_shard(env, index, shard, &farm_shard);
 
-                block.normalized_total -=
-                    prev_normalized_gap + prev_normalized_stake + prev_normalized_zeros;
+    if planted {
+        let mut indexes = get_farmer_pails(env, farmer.clone());
+
+        // Pails are temporary, drop the oldest ones once they've been evicted without a harvest
+        while let Some(oldest) = indexes.first() {
+            if has_pail(env, farmer.clone(), oldest) {
+                break;
             }
-            None => {
-                // Reclaim the stake from the work step
-                block.staked_total -= pail.stake;
+
+            indexes.pop_front();
+        }
+
+        indexes.push_back(index);
+
+        set_farmer_pails(env, farmer.clone(), &indexes);
+    }
+
+    if planted {
+        stats.blocks_planted += 1;
+    }
+
+    stats.total_staked += amount;
+    stats.last_active_index = stats.last_active_index.max(index);
+
+    set_farmer_stats(env, farmer.clone(), &stats);
+
+    extend_instance_ttl(env);
+
+    index
+}
+
+/// Submit the `farmer`'s `hash` for the current block, or for the previous block during its grace period when `grace` is set
+///
+/// Returns the ledger gap between the pail's `plant` and this `work`
+pub fn work_pail(env: &Env, farmer: &Address, hash: BytesN<32>, nonce: u64, grace: bool) -> u32 {
+    let mut index = get_farm_index(env);
+    let mut block =
+        get_block(env, index).unwrap_or_else(|| panic_with_error!(env, &Errors::BlockMissing));
+    let mut generated_hash = generate_hash(env, &index, &nonce, &block.entropy, farmer);
+    let mut late = false;
+
+    // For `grace_period` ledgers after a rollover a hash for the previous block is still accepted
+    if hash != generated_hash && grace && in_grace_period(env, index) {
+        if let Some(prev_block) = get_block(env, index - 1) {
+            let prev_hash = generate_hash(env, &(index - 1), &nonce, &prev_block.entropy, farmer);
+
+            if hash == prev_hash {
+                index -= 1;
+                block = prev_block;
+                generated_hash = prev_hash;
+                late = true;
             }
         }
+    }
+
+    let mut pail = get_pail(env, farmer.clone(), index)
+        .unwrap_or_else(|| panic_with_error!(env, &Errors::PailMissing));
+    let sequence = env.ledger().sequence();
+    let gap = sequence - pail.sequence;
+
+    // Ensure there's at least 1 ledger gap between plant and work (sorry RowBear, you're a genius)
+    if gap == 0 {
+        panic_with_error!(env, &Errors::GapCountTooLow);
+    }
 
-        farm_block.entropy = generated_hash;
+    // TODO No real reason to check if the hash is valid. If it's not the zero count would just be low or nil which is fine
+    if hash != generated_hash {
+        panic_with_error!(env, &Errors::HashInvalid);
+    }
+
+    let zeros = count_leading_zeros(&hash);
+
+    // Zero effort pails below the block's floor don't get to stretch its envelope
+    if zeros < block.zeros_floor {
+        panic_with_error!(env, &Errors::ZeroCountTooLow);
+    }
+
+    // TODO save per farmer normalizations to their Pail so we don't have to recalculate during harvest
+    // Would allow us to upgrade the normalizations logic without needing to toss the active block
 
-        if gap > farm_block.max_gap {
-            farm_block.max_gap = gap;
+    let (normalized_gap, normalized_stake, normalized_zeros, normalized_streak) =
+        generate_normalizations(env, &block, gap, pail.stake, zeros, pail.streak);
+
+    let mut normalized_total =
+        normalized_gap + normalized_stake + normalized_zeros + normalized_streak;
+    let mut staked_total = 0;
+    let mut worked_count = 0;
+    let mut zeros_total = zeros;
+
+    match (pail.gap, pail.zeros) {
+        (Some(prev_gap), Some(prev_zeros)) => {
+            if zeros <= prev_zeros {
+                panic_with_error!(env, &Errors::ZeroCountTooLow);
+            }
+
+            // Back out exactly what the previous `work` added so the block total always matches what `harvest` will compute
+            let (
+                prev_normalized_gap,
+                prev_normalized_stake,
+                prev_normalized_zeros,
+                prev_normalized_streak,
+            ) = generate_normalizations(env, &block, prev_gap, pail.stake, prev_zeros, pail.streak);
+
+            normalized_total -= prev_normalized_gap
+                + prev_normalized_stake
+                + prev_normalized_zeros
+                + prev_normalized_streak;
+            zeros_total -= prev_zeros;
+        }
+        _ => {
+            // Reclaim the stake from the work step
+            // Shard totals may go negative (e.g. a pail planted into another shard before sharding), only their sum is meaningful
+            staked_total -= pail.stake;
+            worked_count += 1;
         }
+    }
+
+    if late {
+        // The previous block was already folded at rollover and its observations fed the current block
+        // so late work only adjusts its totals, and `harvest` waits out the grace period before trusting them
+        block.normalized_total += normalized_total;
+        block.staked_total += staked_total;
+        block.worked_count += worked_count;
+        block.zeros_total += zeros_total;
+
+        count_zeros(&mut block.zeros_counts, pail.zeros, zeros);
 
-        if gap < farm_block.min_gap {
-            farm_block.min_gap = gap;
+        set_block(env, index, &block);
+        set_block_summary(env, index, &new_block_summary(index, &block));
+
+        // The reclaimed stake goes back to the farmer on `harvest`, so it's no longer part of the pool recorded at rollover
+        if let Some(unclaimed) = get_unclaimed(env, index) {
+            set_unclaimed(env, index, unclaimed + staked_total);
+        }
+    } else {
+        // The block itself is read only, all of this farmer's updates land in their shard
+        let shard = get_shard_index(env, farmer);
+        let mut farm_shard = get_shard(env, index, shard).unwrap_or_else(|| new_shard(env));
+
+        // Tickets are numbered within the shard so workers never contend on a shared counter
+        if worked_count > 0 {
+            pail.ticket = Some(farm_shard.worked_count);
         }
 
-        if zeros > farm_block.max_zeros {
-            farm_block.max_zeros = zeros;
+        farm_shard.normalized_total += normalized_total;
+        farm_shard.staked_total += staked_total;
+        farm_shard.worked_count += worked_count;
+        farm_shard.zeros_total += zeros_total;
+        farm_shard.entropy = generated_hash;
+
+        move_tally(
+            &mut farm_shard.zeros_tally,
+            pail.zeros.map(zeros_bucket),
+            zeros_bucket(zeros),
+        );
+
+        if gap > farm_shard.max_gap {
+            farm_shard.max_gap = gap;
         }
 
-        if zeros < farm_block.min_zeros {
-            farm_block.min_zeros = zeros;
+        if gap < farm_shard.min_gap {
+            farm_shard.min_gap = gap;
         }
 
-        pail.gap = Some(gap);
-        pail.zeros = Some(zeros);
+        if zeros > farm_shard.max_zeros {
+            farm_shard.max_zeros = zeros;
+        }
 
-        set_pail(&env, farmer, index, pail);
-        set_block(&env, index, &block);
-        set_farm_block(&env, &farm_block);
+        if zeros < farm_shard.min_zeros {
+            farm_shard.min_zeros = zeros;
+        }
 
-        extend_instance_ttl(&env);
+        // Only a strictly better hash takes over so the earliest keeps a tie
+        if farm_shard.best_ticket == u32::MAX || zeros > farm_shard.best_zeros {
+            farm_shard.best_zeros = zeros;
+            farm_shard.best_sequence = sequence;
+            farm_shard.best_ticket = pail.ticket.unwrap_or(u32::MAX);
+        }
 
-        gap
+        set_shard(env, index, shard, &farm_shard);
     }
 
-    fn harvest(env: Env, farmer: Address, index: u32) -> i128 {
-        let asset = get_farm_asset(&env);
-        let farm_index = get_farm_index(&env);
-        let block = get_block(&env, index)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BlockMissing));
-        let Pail {
-            gap, stake, zeros, ..
-        } = get_pail(&env, farmer.clone(), index)
-            .unwrap_or_else(|| panic_with_error!(&env, &Errors::PailMissing));
+    let mut stats = get_farmer_stats(env, farmer.clone());
 
-        if index >= farm_index {
-            panic_with_error!(&env, &Errors::HarvestNotReady);
-        }
+    // Re-work only improves a pail, each block counts once towards the farmer's streak
+    if pail.zeros.is_none() {
+        stats.blocks_worked += 1;
 
-        if gap.is_none() || zeros.is_none() {
-            panic_with_error!(&env, &Errors::WorkMissing);
+        if stats.streak == 0 || index > stats.last_worked_index + 1 {
+            stats.streak = 1;
+        } else if index == stats.last_worked_index + 1 {
+            stats.streak += 1;
         }
 
-        let gap = gap.unwrap();
-        let zeros = zeros.unwrap();
+        stats.last_worked_index = stats.last_worked_index.max(index);
+        stats.longest_streak = stats.longest_streak.max(stats.streak);
+    }
 
-        let (normalized_gap, normalized_stake, normalized_zeros) =
-            generate_normalizations(&env, &block, gap, stake, zeros);
+    stats.best_zeros = stats.best_zeros.max(zeros);
+    stats.last_active_index = stats.last_active_index.max(index);
 
-        // Calculate the decayed block reward
-        // Calculated dynamically per harvest (vs in the instance) as each block may have its own reward depending on when a user harvests
-        let block_reward = calculate_block_reward(&env, index);
+    set_farmer_stats(env, farmer.clone(), &stats);
 
-        let reward = (normalized_gap + normalized_stake + normalized_zeros).fixed_mul_floor(
-            &env,
-            &(block_reward + block.staked_total),
-            &block.normalized_total.max(1),
-        );
-        let reward_and_stake = reward + stake;
+    pail.gap = Some(gap);
+    pail.zeros = Some(zeros);
 
-        if reward_and_stake > 0 {
-            token::StellarAssetClient::new(&env, &asset).mint(&farmer, &reward_and_stake);
-        }
+    set_pail(env, farmer.clone(), index, pail);
 
-        remove_pail(&env, farmer.clone(), index);
+    extend_instance_ttl(env);
 
-        extend_instance_ttl(&env);
+    gap
+}
 
-        reward
+/// A worked pail for `index` can be harvested once its block has closed and, for the previous block, its grace period is over
+pub fn is_harvestable(env: &Env, farm_index: u32, index: u32) -> bool {
+    // The previous block's totals can still move until its grace period is over
+    index < farm_index && !(index + 1 == farm_index && in_grace_period(env, farm_index))
+}
+
+/// The current block closes after its `ledgers` when set, otherwise after its `interval` seconds
+fn is_block_closed(env: &Env, block: &Block) -> bool {
+    if block.ledgers > 0 {
+        // The rollover sequence is when the current block was opened
+        env.ledger().sequence() >= get_farm_rollover(env).saturating_add(block.ledgers)
+    } else {
+        env.ledger().timestamp() >= block.timestamp + block.interval
     }
 }
 
+/// Planting into the current block has closed the previous one but it still accepts late `work` for `grace_period` ledgers
+pub fn in_grace_period(env: &Env, index: u32) -> bool {
+    index > 0
+        && env.ledger().sequence()
+            < get_farm_rollover(env).saturating_add(get_farm_config(env).grace_period)
+}
+
 pub fn new_farm_block(env: &Env) -> Block {
     Block {
         timestamp: env.ledger().timestamp(),
@@ -252,10 +633,197 @@ pub fn new_farm_block(env: &Env) -> Block {
         entropy: BytesN::from_array(env, &[0; 32]),
         staked_total: 0,
         normalized_total: 0,
+        planted_count: 0,
+        worked_count: 0,
+        harvested_count: 0,
+        total_reward_minted: 0,
+        zeros_total: 0,
+        gap_weight: 0,
+        stake_weight: 0,
+        zeros_weight: 0,
+        stake_curve: Curve::Linear,
+        zeros_curve: Curve::Linear,
+        gap_cap: 0,
+        planted_total: 0,
+        stake_cap: i128::MAX,
+        zeros_counts: Vec::from_array(env, [0; ZEROS_BUCKETS as usize]),
+        stake_counts: Vec::from_array(env, [0; STAKE_BUCKETS as usize]),
+        zeros_floor: 0,
+        interval: 0,
+        ledgers: 0,
+        carry: 0,
+        block_reward: 0,
+        lottery_share: 0,
+        lottery_shard: 0,
+        lottery_ticket: u32::MAX,
+        jackpot_share: 0,
+        best_zeros: 0,
+        best_sequence: 0,
+        best_shard: 0,
+        best_ticket: u32::MAX,
+    }
+}
+
+/// An empty shard, every min and max ready to be narrowed by the first `plant` or `work`
+pub fn new_shard(env: &Env) -> Shard {
+    Shard {
+        min_gap: u32::MAX,
+        min_stake: i128::MAX,
+        min_zeros: u32::MAX,
+        max_gap: u32::MIN,
+        max_stake: i128::MIN,
+        max_zeros: u32::MIN,
+        entropy: BytesN::from_array(env, &[0; 32]),
+        staked_total: 0,
+        normalized_total: 0,
+        planted_total: 0,
+        planted_count: 0,
+        worked_count: 0,
+        harvested_count: 0,
+        total_reward_minted: 0,
+        zeros_total: 0,
+        zeros_tally: BytesN::from_array(env, &[0; ZEROS_BUCKETS as usize * 2]),
+        stake_tally: BytesN::from_array(env, &[0; STAKE_BUCKETS as usize * 2]),
+        best_zeros: 0,
+        best_sequence: 0,
+        best_ticket: u32::MAX,
+    }
+}
+
+/// Pre-create the block at `index` and its shards with the same fixed-size entries they'll eventually hold
+fn set_placeholders(env: &Env, index: u32) {
+    // A zero timestamp marks the block as not yet opened, it's overwritten when `index` becomes the farm index
+    let block = Block {
+        timestamp: 0,
+        ..new_block(env, index, &new_farm_block(env))
+    };
+
+    set_block(env, index, &block);
+
+    for shard in 0..BLOCK_SHARDS {
+        set_shard(env, index, shard, &new_shard(env));
+    }
+}
+
+/// Pick the shard a farmer's `plant` and `work` updates accumulate in
+pub fn get_shard_index(env: &Env, farmer: &Address) -> u32 {
+    let farmer_bytes = farmer.to_xdr(env);
+
+    farmer_bytes.get_unchecked(farmer_bytes.len() - 1) as u32 % BLOCK_SHARDS
+}
+
+/// Merge every shard of the closing block, adding their totals into `block` and returning their combined observations
+///
+/// The returned entropy hashes every shard's last `work` so it's the same no matter which order the shards were written in
+fn fold_shards(env: &Env, index: u32, block: &mut Block) -> Block {
+    let mut farm_block = new_farm_block(env);
+    let mut entropy = Bytes::new(env);
+    let mut tickets = [0; BLOCK_SHARDS as usize];
+
+    // The next block's interval and zeros floor step from this one's
+    farm_block.interval = block.interval;
+    farm_block.zeros_floor = block.zeros_floor;
+
+    // Blocks opened before sharding kept their observations in the instance and never handed out tickets
+    let legacy = get_farm_block(env);
+
+    // Every block up to this one may still have its `LegacyBlock` and legacy pails around
+    if legacy.is_some() {
+        set_farm_legacy(env, index);
+    }
+
+    let shards = legacy.map(|totals| (None, totals)).into_iter().chain(
+        (0..BLOCK_SHARDS).filter_map(|i| get_shard(env, index, i).map(|shard| (Some(i), shard))),
+    );
+
+    for (i, shard) in shards {
+        if let Some(i) = i {
+            tickets[i as usize] = shard.worked_count;
+
+            add_best_hash(block, i, &shard);
+        }
+
+        block.staked_total += shard.staked_total;
+        block.normalized_total += shard.normalized_total;
+        block.planted_count += shard.planted_count;
+        block.worked_count += shard.worked_count;
+        block.zeros_total += shard.zeros_total;
+        block.planted_total += shard.planted_total;
+
+        add_tally(&mut block.zeros_counts, &shard.zeros_tally);
+        add_tally(&mut farm_block.zeros_counts, &shard.zeros_tally);
+        add_tally(&mut block.stake_counts, &shard.stake_tally);
+        add_tally(&mut farm_block.stake_counts, &shard.stake_tally);
+
+        // The next block's stake cap is based on what was planted into this one
+        farm_block.planted_count += shard.planted_count;
+        farm_block.planted_total += shard.planted_total;
+        farm_block.worked_count += shard.worked_count;
+
+        farm_block.min_gap = farm_block.min_gap.min(shard.min_gap);
+        farm_block.min_stake = farm_block.min_stake.min(shard.min_stake);
+        farm_block.min_zeros = farm_block.min_zeros.min(shard.min_zeros);
+        farm_block.max_gap = farm_block.max_gap.max(shard.max_gap);
+        farm_block.max_stake = farm_block.max_stake.max(shard.max_stake);
+        farm_block.max_zeros = farm_block.max_zeros.max(shard.max_zeros);
+
+        entropy.append(&shard.entropy.into());
+    }
+
+    if !entropy.is_empty() {
+        farm_block.entropy = env.crypto().keccak256(&entropy).to_bytes();
+    }
+
+    if block.lottery_share > 0 {
+        draw_lottery(env, block, &farm_block.entropy, &tickets);
+    }
+
+    remove_farm_block(env);
+
+    farm_block
+}
+
+/// Draw a single ticket from every shard's worked pails, seeded by the entropy the closing block's work produced
+fn draw_lottery(env: &Env, block: &mut Block, entropy: &BytesN<32>, tickets: &[u32]) {
+    let total = tickets.iter().sum::<u32>();
+
+    if total == 0 {
+        return;
+    }
+
+    env.prng().seed(entropy.clone().into());
+
+    let mut ticket = env.prng().gen_range::<u64>(0..total as u64) as u32;
+
+    for (shard, count) in tickets.iter().enumerate() {
+        if ticket < *count {
+            block.lottery_shard = shard as u32;
+            block.lottery_ticket = ticket;
+
+            return;
+        }
+
+        ticket -= count;
+    }
+}
+
+fn new_block_summary(index: u32, block: &Block) -> BlockSummary {
+    BlockSummary {
+        index,
+        timestamp: block.timestamp,
+        entropy: block.entropy.clone(),
+        staked_total: block.staked_total,
+        normalized_total: block.normalized_total,
+        reward: block.block_reward + block.carry,
+        participants: block.worked_count,
     }
 }
 
-fn new_block(env: &Env, farm_block: &Block) -> Block {
+fn new_block(env: &Env, index: u32, farm_block: &Block) -> Block {
+    // Snapshot the weights and reward so a `set_config` mid-block can't change how it settles
+    let config = get_farm_config(env);
+    let interval = calculate_interval(env, &config, farm_block);
+
     // Autofill any non-default values with any current farm_block values we've got
     Block {
         timestamp: env.ledger().timestamp(),
@@ -292,10 +860,55 @@ fn new_block(env: &Env, farm_block: &Block) -> Block {
         entropy: farm_block.entropy.clone(),
         staked_total: 0,
         normalized_total: 0,
+        planted_count: 0,
+        worked_count: 0,
+        harvested_count: 0,
+        total_reward_minted: 0,
+        zeros_total: 0,
+        gap_weight: config.gap_weight,
+        stake_weight: config.stake_weight,
+        zeros_weight: config.zeros_weight,
+        stake_curve: config.stake_curve,
+        zeros_curve: config.zeros_curve,
+        gap_cap: config.gap_cap,
+        planted_total: 0,
+        stake_cap: calculate_stake_cap(env, &config.stake_cap, farm_block),
+        zeros_counts: Vec::from_array(env, [0; ZEROS_BUCKETS as usize]),
+        stake_counts: Vec::from_array(env, [0; STAKE_BUCKETS as usize]),
+        zeros_floor: calculate_zeros_floor(env, &config, farm_block),
+        interval,
+        ledgers: config.block_ledgers,
+        // Whatever earlier blocks left behind is paid out by this one
+        carry: take_farm_carry(env),
+        block_reward: calculate_block_reward(env, &config, index, interval),
+        lottery_share: config.lottery_share,
+        lottery_shard: 0,
+        lottery_ticket: u32::MAX,
+        jackpot_share: config.jackpot_share,
+        best_zeros: 0,
+        best_sequence: 0,
+        best_shard: 0,
+        best_ticket: u32::MAX,
+    }
+}
+
+/// Count the leading zero nibbles of a `hash`
+pub fn count_leading_zeros(hash: &BytesN<32>) -> u32 {
+    let mut zeros = 0;
+
+    for byte in hash.iter() {
+        if byte == 0 {
+            zeros += 2;
+        } else {
+            zeros += byte.leading_zeros() / 4;
+            break;
+        }
     }
+
+    zeros
 }
 
-fn generate_hash(
+pub fn generate_hash(
     env: &Env,
     index: &u32,
     nonce: &u64,
@@ -326,7 +939,8 @@ fn generate_normalizations(
     gap: u32,
     stake: i128,
     zeros: u32,
-) -> (i128, i128, i128) {
+    streak: u32,
+) -> (i128, i128, i128, i128) {
     // Prevent division by zero by ensuring max >= min for each range.
     // TODO should be impossible to hit (consider dropping)
     if block.max_gap < block.min_gap
@@ -336,42 +950,351 @@ fn generate_normalizations(
         panic_with_error!(&env, &Errors::BlockInvalid);
     }
 
+    // Run each value and its block's envelope through the block's curve, measuring from the minimum
+    let gap_cap = if block.gap_cap == 0 {
+        u32::MAX
+    } else {
+        block.gap_cap
+    };
+    let (range_gap, scored_gap) = score(
+        Curve::Linear,
+        block.min_gap.min(gap_cap) as i128,
+        block.max_gap.min(gap_cap) as i128,
+        gap.min(gap_cap) as i128,
+    );
+    let (range_stake, scored_stake) =
+        score(block.stake_curve, block.min_stake, block.max_stake, stake);
+    let (range_zeros, scored_zeros) = score(
+        block.zeros_curve,
+        block.min_zeros as i128,
+        block.max_zeros as i128,
+        zeros as i128,
+    );
+
     // Calculate ranges
-    let range_gap = (block.max_gap - block.min_gap).max(1) as i128;
-    let range_stake = (block.max_stake - block.min_stake).max(1);
-    let range_zeros = (block.max_zeros - block.min_zeros).max(1) as i128;
+    let range_gap = range_gap.max(1);
+    let range_stake = range_stake.max(1);
+    let range_zeros = range_zeros.max(1);
 
     // Find largest range for scaling
     let normalization_scale = range_gap.max(range_stake).max(range_zeros);
 
-    // Clamp each value within its range.
-    let clamped_gap = gap.max(block.min_gap).min(block.max_gap);
-    let clamped_stake = stake.max(block.min_stake).min(block.max_stake);
-    let clamped_zeros = zeros.max(block.min_zeros).min(block.max_zeros);
-
     // Normalize each value by subtracting the minimum and scaling relative to the range size.
-    let normalized_gap = ((clamped_gap - block.min_gap) as i128)
-        .fixed_mul_floor(&env, &normalization_scale, &range_gap)
-        .max(1);
-    let normalized_stake = (clamped_stake - block.min_stake)
-        .fixed_mul_floor(&env, &normalization_scale, &range_stake)
-        .max(1);
-    let normalized_zeros = ((clamped_zeros - block.min_zeros) as i128)
-        .fixed_mul_floor(&env, &normalization_scale, &range_zeros)
-        .max(1);
+    // Then weigh each term by the block's snapshotted weight
+    let normalized_gap = scored_gap
+        .fixed_mul_floor(env, &normalization_scale, &range_gap)
+        .max(1)
+        .fixed_mul_floor(env, &(block.gap_weight as i128), &(BASIS_POINTS as i128));
+    let normalized_stake = scored_stake
+        .fixed_mul_floor(env, &normalization_scale, &range_stake)
+        .max(1)
+        .fixed_mul_floor(env, &(block.stake_weight as i128), &(BASIS_POINTS as i128));
+    let normalized_zeros = scored_zeros
+        .fixed_mul_floor(env, &normalization_scale, &range_zeros)
+        .max(1)
+        .fixed_mul_floor(env, &(block.zeros_weight as i128), &(BASIS_POINTS as i128));
+
+    // The streak bonus tops out at one full range so it can tip a close block but never outweigh the work itself
+    // No floor here, farmers without a streak simply don't get the extra term
+    let normalized_streak =
+        (streak as i128).fixed_mul_floor(env, &normalization_scale, &(BASIS_POINTS as i128));
+
+    (
+        normalized_gap,
+        normalized_stake,
+        normalized_zeros,
+        normalized_streak,
+    )
+}
+
+/// Clamp `value` within `min` and `max` and score it on `curve`, returning the scored range and the value's distance from the bottom of it
+fn score(curve: Curve, min: i128, max: i128, value: i128) -> (i128, i128) {
+    let value = value.max(min).min(max);
+
+    match curve {
+        Curve::Linear => (max - min, value - min),
+        Curve::Sqrt => (sqrt(max) - sqrt(min), sqrt(value) - sqrt(min)),
+        // Offset by one so nothing staked still scores the bottom of the curve
+        Curve::Log => (
+            log2(max.saturating_add(1)) - log2(min + 1),
+            log2(value.saturating_add(1)) - log2(min + 1),
+        ),
+        // Relative to the minimum, only the spread of zeros matters and it's what keeps this from overflowing
+        Curve::Exp => (
+            pow16((max - min) as u32) - 1,
+            pow16((value - min) as u32) - 1,
+        ),
+    }
+}
+
+/// Resolve the config's stake cap for a block opened after `farm_block`, `i128::MAX` when uncapped
+fn calculate_stake_cap(env: &Env, stake_cap: &StakeCap, farm_block: &Block) -> i128 {
+    match stake_cap {
+        StakeCap::None => i128::MAX,
+        StakeCap::Absolute(cap) => *cap,
+        StakeCap::Median(multiple) => {
+            let target = farm_block.planted_count.div_ceil(2);
+            let mut reached = 0;
+
+            // Walk up from the smallest stakes until half the planters are at or below the bucket
+            for bucket in 0..STAKE_BUCKETS {
+                reached += farm_block.stake_counts.get(bucket).unwrap_or(0);
+
+                if reached >= target {
+                    // Nothing to go off of after an empty block, a block that mostly staked nothing or one whose median overflowed the histogram, leave the next one open
+                    if target == 0 || bucket == 0 || bucket == STAKE_BUCKETS - 1 {
+                        break;
+                    }
+
+                    // The largest stake in the bucket, so the cap never falls below the true median
+                    let median = (1i128 << bucket) - 1;
+
+                    return median.fixed_mul_floor(
+                        env,
+                        &(*multiple as i128),
+                        &(BASIS_POINTS as i128),
+                    );
+                }
+            }
+
+            i128::MAX
+        }
+    }
+}
+
+/// Resolve the config's zeros floor for a block opened after `farm_block`
+///
+/// The floor is the most zeros that `zeros_target` of the previous block's planters reached, bounded by the config
+///
+/// Only work that cleared the previous floor is in the histogram, so when too few planters did the floor steps back down
+fn calculate_zeros_floor(env: &Env, config: &FarmConfig, farm_block: &Block) -> u32 {
+    let mut floor = 0;
+
+    if config.zeros_target > 0 && farm_block.planted_count > 0 {
+        let target = (farm_block.planted_count as i128).fixed_mul_ceil(
+            env,
+            &(config.zeros_target as i128),
+            &(BASIS_POINTS as i128),
+        ) as u32;
+        let mut reached = 0;
+
+        floor = farm_block.zeros_floor.saturating_sub(1);
+
+        // Walk down from the most zeros until enough planters are at or above the bucket
+        for bucket in (0..ZEROS_BUCKETS).rev() {
+            reached += farm_block.zeros_counts.get(bucket).unwrap_or(0);
+
+            if reached >= target {
+                floor = bucket;
+                break;
+            }
+        }
+    }
+
+    floor
+        .max(config.zeros_floor_min)
+        .min(config.zeros_floor_max)
+}
+
+/// The histogram bucket of a pail's `zeros`, the last one also counts every pail above it
+fn zeros_bucket(zeros: u32) -> u32 {
+    zeros.min(ZEROS_BUCKETS - 1)
+}
+
+/// The histogram bucket of a pail's `stake`, its bit length, so bucket `b` holds the stakes below `2^b`
+fn stake_bucket(stake: i128) -> u32 {
+    (i128::BITS - stake.leading_zeros()).min(STAKE_BUCKETS - 1)
+}
+
+/// Move a pail in a zeros histogram from its `prev_zeros` bucket, if it was already worked, to its new `zeros` bucket
+fn count_zeros(counts: &mut Vec<u32>, prev_zeros: Option<u32>, zeros: u32) {
+    if let Some(prev_zeros) = prev_zeros {
+        let bucket = zeros_bucket(prev_zeros);
+
+        counts.set(bucket, counts.get_unchecked(bucket).saturating_sub(1));
+    }
+
+    let bucket = zeros_bucket(zeros);
+
+    counts.set(bucket, counts.get_unchecked(bucket) + 1);
+}
+
+fn get_tally<const N: usize>(tally: &BytesN<N>, bucket: u32) -> u16 {
+    u16::from_be_bytes([
+        tally.get_unchecked(bucket * 2),
+        tally.get_unchecked(bucket * 2 + 1),
+    ])
+}
+
+fn set_tally<const N: usize>(tally: &mut BytesN<N>, bucket: u32, count: u16) {
+    let [high, low] = count.to_be_bytes();
+
+    tally.set(bucket * 2, high);
+    tally.set(bucket * 2 + 1, low);
+}
+
+/// Move a pail in a shard's tally from its `prev` bucket, if it was already counted, to its new `bucket`
+fn move_tally<const N: usize>(tally: &mut BytesN<N>, prev: Option<u32>, bucket: u32) {
+    if let Some(prev) = prev {
+        set_tally(tally, prev, get_tally(tally, prev).saturating_sub(1));
+    }
+
+    set_tally(tally, bucket, get_tally(tally, bucket).saturating_add(1));
+}
+
+/// Add a shard's tally into a block's histogram of the same buckets
+pub fn add_tally<const N: usize>(counts: &mut Vec<u32>, tally: &BytesN<N>) {
+    for bucket in 0..counts.len() {
+        counts.set(
+            bucket,
+            counts.get_unchecked(bucket) + get_tally(tally, bucket) as u32,
+        );
+    }
+}
+
+/// Keep whichever of the block's and the `shard`'s best hash has the most zeros, or was submitted first on a tie
+pub fn add_best_hash(block: &mut Block, shard: u32, totals: &Shard) {
+    if totals.best_ticket != u32::MAX
+        && (block.best_ticket == u32::MAX
+            || totals.best_zeros > block.best_zeros
+            || (totals.best_zeros == block.best_zeros
+                && totals.best_sequence < block.best_sequence))
+    {
+        block.best_zeros = totals.best_zeros;
+        block.best_sequence = totals.best_sequence;
+        block.best_shard = shard;
+        block.best_ticket = totals.best_ticket;
+    }
+}
+
+/// Scale the farmer's current work streak into a basis point bonus, capped at the config's `streak_cap`
+///
+/// Only a streak that ended with the previous block carries into `index`
+fn calculate_streak_bonus(env: &Env, stats: &FarmerStats, index: u32) -> u32 {
+    let streak_cap = get_farm_config(env).streak_cap;
+
+    if streak_cap == 0 || stats.streak == 0 || stats.last_worked_index + 1 != index {
+        return 0;
+    }
+
+    stats.streak.min(streak_cap) * BASIS_POINTS / streak_cap
+}
+
+/// Resolve the config's interval for a block opened after `farm_block`
+///
+/// Busier than `activity_target` and the next block stays open longer, quieter and it closes sooner
+fn calculate_interval(env: &Env, config: &FarmConfig, farm_block: &Block) -> u64 {
+    // A ledger block lasts, and is paid for, the time its ledgers nominally take so emissions per second stay the same
+    if config.block_ledgers > 0 {
+        return config.block_ledgers as u64 * LEDGER_SECONDS;
+    }
+
+    // Genesis or a fixed interval
+    if config.activity_target == 0 || farm_block.interval == 0 {
+        return config.block_interval;
+    }
+
+    let interval = farm_block.interval as i128;
+    let ledgers = env
+        .ledger()
+        .sequence()
+        .saturating_sub(get_farm_rollover(env))
+        .max(1) as i128;
+    let activity = (farm_block.planted_count + farm_block.worked_count) as i128;
+    let activity_rate = activity.fixed_mul_floor(env, &(BASIS_POINTS as i128), &ledgers);
+    let step = interval.fixed_mul_floor(env, &(INTERVAL_STEP as i128), &(BASIS_POINTS as i128));
+
+    interval
+        .fixed_mul_floor(env, &activity_rate, &(config.activity_target as i128))
+        .max(interval - step)
+        .min(interval + step)
+        .max(config.interval_min as i128)
+        .min(config.interval_max as i128) as u64
+}
 
-    (normalized_gap, normalized_stake, normalized_zeros)
+/// The decayed reward for a block open for `interval` seconds, `block_reward` is paid per `block_interval`
+pub fn calculate_block_reward(env: &Env, config: &FarmConfig, index: u32, interval: u64) -> i128 {
+    calculate_scheduled_reward(env, config, index).fixed_mul_floor(
+        env,
+        &(interval as i128),
+        &(config.block_interval as i128),
+    )
 }
 
-fn calculate_block_reward(env: &Env, index: u32) -> i128 {
-    let elapsed_time = index.saturating_sub(V2_GENESIS_BLOCK);
-    let periods = elapsed_time.saturating_div(BLOCKS_PER_MONTH);
+/// Roll whatever's left of a settled block's pool into the next block opened
+fn carry_remainder(env: &Env, index: u32, block: &Block) {
+    let remainder =
+        block.block_reward + block.carry + block.staked_total - block.total_reward_minted;
 
-    let mut result = BLOCK_SCALE;
+    // Nothing is left to sweep
+    remove_unclaimed(env, index);
 
-    for _ in 0..periods {
-        result = result.fixed_mul_floor(env, &INVERSE_DECAY_RATE, &BLOCK_SCALE);
+    if remainder > 0 {
+        set_farm_carry(env, get_farm_carry(env) + remainder);
+    }
+}
+
+fn take_farm_carry(env: &Env) -> i128 {
+    let carry = get_farm_carry(env);
+
+    if carry > 0 {
+        set_farm_carry(env, 0);
+    }
+
+    carry
+}
+
+/// The block reward the emission schedule sets for `index`, before it's scaled by the block's interval
+pub fn calculate_scheduled_reward(env: &Env, config: &FarmConfig, index: u32) -> i128 {
+    // Without eras the farm runs a single compounding decay from `genesis_block`
+    let mut era = config.eras.first().unwrap_or(Era {
+        start: config.genesis_block,
+        reward: config.block_reward,
+        floor: 0,
+        schedule: Schedule::Decay(config.decay_period, config.decay_rate),
+    });
+
+    // Blocks before the first era are paid as if it had just started
+    for next in config.eras.iter() {
+        if next.start > index {
+            break;
+        }
+
+        era = next;
     }
 
-    BLOCK_REWARD.fixed_mul_floor(env, &result, &BLOCK_SCALE)
+    let elapsed_time = index.saturating_sub(era.start);
+
+    let reward = match era.schedule {
+        Schedule::Constant => era.reward,
+        Schedule::Decay(period, rate) => {
+            let periods = elapsed_time.saturating_div(period);
+
+            // Compounding a period at a time rounds exactly as the farm always has, squaring only bounds the work for longer schedules
+            let result = if periods <= DECAY_STEPS {
+                let mut result = BLOCK_SCALE;
+
+                for _ in 0..periods {
+                    if result == 0 {
+                        break;
+                    }
+
+                    result = result.fixed_mul_floor(env, &(BLOCK_SCALE - rate), &BLOCK_SCALE);
+                }
+
+                result
+            } else {
+                pow_scaled(BLOCK_SCALE - rate, periods, BLOCK_SCALE)
+            };
+
+            era.reward.fixed_mul_floor(env, &result, &BLOCK_SCALE)
+        }
+        Schedule::Halving(period) => era.reward >> elapsed_time.saturating_div(period).min(127),
+        Schedule::Linear(period, step) => era
+            .reward
+            .saturating_sub(step.saturating_mul(elapsed_time.saturating_div(period) as i128))
+            .max(0),
+    };
+
+    reward.max(era.floor)
 }
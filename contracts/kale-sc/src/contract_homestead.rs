@@ -1,4 +1,4 @@
-use crate::{contract_farm::new_farm_block, storage::set_farm_block, ContractArgs};
+use crate::ContractArgs;
 use soroban_sdk::{
     auth::{Context, CustomAccountInterface},
     contractimpl,
@@ -10,15 +10,16 @@ use crate::{
     errors::Errors,
     storage::{
         extend_instance_ttl, get_farm_homesteader, get_farm_paused, has_farm_homesteader,
-        set_farm_asset, set_farm_homesteader, set_farm_paused,
+        remove_block, set_farm_asset, set_farm_config, set_farm_homesteader, set_farm_paused,
+        set_farm_treasury,
     },
-    types::Storage,
-    Contract, ContractClient, HomesteadTrait,
+    types::{Curve, FarmConfig, Schedule, StakeCap, Treasury},
+    Contract, ContractClient, HomesteadTrait, BASIS_POINTS, BLOCK_SCALE,
 };
 
 #[contractimpl]
 impl HomesteadTrait for Contract {
-    fn __constructor(env: Env, farmer: Address, asset: Address) {
+    fn __constructor(env: Env, farmer: Address, asset: Address, config: Option<FarmConfig>) {
         farmer.require_auth();
 
         if has_farm_homesteader(&env) {
@@ -27,7 +28,11 @@ impl HomesteadTrait for Contract {
 
         set_farm_homesteader(&env, &farmer);
         set_farm_asset(&env, &asset);
-        set_farm_block(&env, &new_farm_block(&env));
+
+        if let Some(config) = config {
+            check_config(&env, &config);
+            set_farm_config(&env, &config);
+        }
 
         extend_instance_ttl(&env);
     }
@@ -67,7 +72,81 @@ impl HomesteadTrait for Contract {
     fn remove_block(env: Env, index: u32) {
         get_farm_homesteader(&env).require_auth();
 
-        env.storage().temporary().remove(&Storage::Block(index));
+        remove_block(&env, index);
+    }
+
+    fn set_treasury(env: Env, treasury: Address, fee: u32) {
+        get_farm_homesteader(&env).require_auth();
+
+        if fee > BASIS_POINTS {
+            panic_with_error!(&env, &Errors::TreasuryFeeTooHigh);
+        }
+
+        set_farm_treasury(
+            &env,
+            &Treasury {
+                address: treasury,
+                fee,
+            },
+        );
+
+        extend_instance_ttl(&env);
+    }
+
+    fn set_config(env: Env, config: FarmConfig) {
+        get_farm_homesteader(&env).require_auth();
+
+        check_config(&env, &config);
+        set_farm_config(&env, &config);
+
+        extend_instance_ttl(&env);
+    }
+}
+
+fn check_config(env: &Env, config: &FarmConfig) {
+    if config.block_interval == 0
+        || config.block_reward < 0
+        || config.decay_period == 0
+        || config.decay_rate < 0
+        || config.decay_rate > BLOCK_SCALE
+        || config.gap_weight > BASIS_POINTS
+        || config.stake_weight > BASIS_POINTS
+        || config.zeros_weight > BASIS_POINTS
+        || config.gap_weight + config.stake_weight + config.zeros_weight == 0
+        || config.stake_curve == Curve::Exp
+        || matches!(config.stake_cap, StakeCap::Absolute(cap) if cap < 0)
+        // A cap below the median would shrink every block until nothing can be staked
+        || matches!(config.stake_cap, StakeCap::Median(multiple) if multiple < BASIS_POINTS)
+        || config.zeros_target > BASIS_POINTS
+        || config.zeros_floor_min > config.zeros_floor_max
+        || (config.activity_target > 0
+            && (config.interval_min == 0
+                || config.interval_min > config.block_interval
+                || config.interval_max < config.block_interval))
+        // Ledger blocks have a fixed length so there's no interval to adapt
+        || (config.activity_target > 0 && config.block_ledgers > 0)
+        || config.lottery_share + config.jackpot_share > BASIS_POINTS
+    {
+        panic_with_error!(env, &Errors::ConfigInvalid);
+    }
+
+    let mut start = None;
+
+    for era in config.eras.iter() {
+        if start.is_some_and(|start| era.start <= start)
+            || era.reward < 0
+            || era.floor < 0
+            || match era.schedule {
+                Schedule::Constant => false,
+                Schedule::Decay(period, rate) => period == 0 || !(0..=BLOCK_SCALE).contains(&rate),
+                Schedule::Halving(period) => period == 0,
+                Schedule::Linear(period, step) => period == 0 || step < 0,
+            }
+        {
+            panic_with_error!(env, &Errors::ConfigInvalid);
+        }
+
+        start = Some(era.start);
     }
 }
 
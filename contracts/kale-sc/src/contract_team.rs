@@ -0,0 +1,89 @@
+use crate::{contract_farm::plant_pail, ContractArgs};
+use soroban_sdk::{contractimpl, panic_with_error, vec, Address, Env, Map};
+
+use crate::{
+    errors::Errors,
+    storage::{
+        extend_instance_ttl, get_team, get_team_member, get_team_pail, has_team,
+        remove_team_member, set_team, set_team_member, set_team_pail,
+    },
+    Contract, ContractClient, TeamTrait, TEAM_SIZE,
+};
+
+#[contractimpl]
+impl TeamTrait for Contract {
+    fn create_team(env: Env, team: Address) {
+        // The team address is what gets hashed in `work`, so whoever controls it must agree to be a team
+        team.require_auth();
+
+        if has_team(&env, team.clone()) {
+            panic_with_error!(&env, &Errors::TeamExists);
+        }
+
+        set_team(&env, team, &vec![&env]);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn join_team(env: Env, farmer: Address, team: Address) {
+        farmer.require_auth();
+        team.require_auth();
+
+        let mut members = get_team(&env, team.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::TeamMissing));
+
+        if get_team_member(&env, farmer.clone()).is_some() {
+            panic_with_error!(&env, &Errors::TeamMemberExists);
+        }
+
+        if members.len() >= TEAM_SIZE {
+            panic_with_error!(&env, &Errors::TeamFull);
+        }
+
+        members.push_back(farmer.clone());
+
+        set_team(&env, team.clone(), &members);
+        set_team_member(&env, farmer, &team);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn leave_team(env: Env, farmer: Address) {
+        farmer.require_auth();
+
+        let team = get_team_member(&env, farmer.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::TeamMemberMissing));
+        let mut members = get_team(&env, team.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::TeamMissing));
+
+        // Stake already planted stays in the team pail and is still paid out to the farmer on `harvest`
+        if let Some(i) = members.first_index_of(&farmer) {
+            members.remove(i);
+        }
+
+        set_team(&env, team, &members);
+        remove_team_member(&env, farmer);
+
+        extend_instance_ttl(&env);
+    }
+
+    fn plant_team(env: Env, farmer: Address, amount: i128) {
+        farmer.require_auth();
+
+        let team = get_team_member(&env, farmer.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::TeamMemberMissing));
+
+        let index = plant_pail(&env, &team, &farmer, amount, true);
+
+        // Record each member's share of the team pail so `harvest` can split the reward by stake
+        let mut stakes = get_team_pail(&env, team.clone(), index).unwrap_or(Map::new(&env));
+
+        if stakes.contains_key(farmer.clone()) {
+            panic_with_error!(&env, &Errors::PailExists);
+        }
+
+        stakes.set(farmer, amount);
+
+        set_team_pail(&env, team, index, &stakes);
+    }
+}
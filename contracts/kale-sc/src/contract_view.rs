@@ -0,0 +1,107 @@
+use crate::{ContractArgs, BLOCK_SHARDS, HISTORY_PAGE, PAILS_PAGE};
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Vec};
+
+use crate::{
+    contract_farm::{add_best_hash, add_tally, calculate_scheduled_reward, is_harvestable},
+    errors::Errors,
+    storage::{
+        get_block, get_block_summary, get_farm_config, get_farm_index, get_farmer_pails,
+        get_farmer_stats, get_pail, get_shard,
+    },
+    types::{Block, BlockSummary, FarmerStats, PendingPail},
+    Contract, ContractClient, ViewTrait,
+};
+
+#[contractimpl]
+impl ViewTrait for Contract {
+    fn farmer_stats(env: Env, farmer: Address) -> FarmerStats {
+        get_farmer_stats(&env, farmer)
+    }
+
+    fn block(env: Env, index: u32) -> Block {
+        let farm_index = get_farm_index(&env);
+
+        // The next block's placeholder isn't open yet
+        if index > farm_index {
+            panic_with_error!(&env, &Errors::BlockMissing);
+        }
+
+        let mut block = get_block(&env, index)
+            .unwrap_or_else(|| panic_with_error!(&env, &Errors::BlockMissing));
+
+        for (i, shard) in
+            (0..BLOCK_SHARDS).filter_map(|i| get_shard(&env, index, i).map(|shard| (i, shard)))
+        {
+            // A closed block's harvests are counted in the harvesting farmers' shards
+            block.harvested_count += shard.harvested_count;
+            block.total_reward_minted += shard.total_reward_minted;
+
+            // The current block's totals and counts live in its shards until rollover folds them in
+            if index == farm_index {
+                block.staked_total += shard.staked_total;
+                block.normalized_total += shard.normalized_total;
+                block.planted_count += shard.planted_count;
+                block.planted_total += shard.planted_total;
+                block.worked_count += shard.worked_count;
+                block.zeros_total += shard.zeros_total;
+
+                add_tally(&mut block.zeros_counts, &shard.zeros_tally);
+                add_tally(&mut block.stake_counts, &shard.stake_tally);
+                add_best_hash(&mut block, i, &shard);
+            }
+        }
+
+        block
+    }
+
+    fn get_block_history(env: Env, from: u32, limit: u32) -> Vec<BlockSummary> {
+        // Only closed blocks have a summary, evicted blocks never got one and are skipped
+        let end = get_farm_index(&env).min(from.saturating_add(limit.min(HISTORY_PAGE)));
+        let mut history = Vec::new(&env);
+
+        for index in from..end {
+            if let Some(summary) = get_block_summary(&env, index) {
+                history.push_back(summary);
+            }
+        }
+
+        history
+    }
+
+    fn pending_pails(env: Env, farmer: Address, from: u32, limit: u32) -> Vec<PendingPail> {
+        let farm_index = get_farm_index(&env);
+        let limit = limit.min(PAILS_PAGE);
+        let mut pails = Vec::new(&env);
+
+        // Indexes are kept in planting order, so a page is the first `limit` pails at or after `from`
+        for index in get_farmer_pails(&env, farmer.clone()).iter() {
+            if pails.len() == limit {
+                break;
+            }
+
+            if index < from {
+                continue;
+            }
+
+            // Skip any pail that's been evicted since it was planted
+            if let Some(pail) = get_pail(&env, farmer.clone(), index) {
+                let worked = pail.zeros.is_some();
+
+                pails.push_back(PendingPail {
+                    index,
+                    stake: pail.stake,
+                    worked,
+                    ready: worked
+                        && is_harvestable(&env, farm_index, index)
+                        && get_block(&env, index).is_some(),
+                });
+            }
+        }
+
+        pails
+    }
+
+    fn block_reward(env: Env, index: u32) -> i128 {
+        calculate_scheduled_reward(&env, &get_farm_config(&env), index)
+    }
+}
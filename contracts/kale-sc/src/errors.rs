@@ -18,4 +18,20 @@ pub enum Errors {
     HashInvalid = 13,
     HarvestNotReady = 14,
     GapCountTooLow = 15,
+    TreasuryFeeTooHigh = 16,
+    BountyExists = 17,
+    BountyMissing = 18,
+    BountyAmountTooLow = 19,
+    BountyNotExpired = 20,
+    TeamExists = 21,
+    TeamMissing = 22,
+    TeamFull = 23,
+    TeamMemberExists = 24,
+    TeamMemberMissing = 25,
+    PailWorked = 26,
+    ConfigInvalid = 27,
+    PlantAmountTooHigh = 28,
+    UnclaimedMissing = 29,
+    BlockNotExpired = 30,
+    BountyCommitMissing = 31,
 }
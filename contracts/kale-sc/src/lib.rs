@@ -1,25 +1,73 @@
 #![no_std]
 
-use soroban_sdk::{contract, Address, BytesN, Env};
+use soroban_sdk::{contract, Address, BytesN, Env, Vec};
+use types::{Block, BlockSummary, Curve, FarmConfig, FarmerStats, PendingPail, StakeCap};
 
+mod contract_bounty;
 mod contract_farm;
 mod contract_homestead;
+mod contract_team;
+mod contract_view;
 mod errors;
+mod math;
 mod storage;
 mod tests;
-mod types;
+pub mod types;
 
 // Higher BLOCK_INTERVAL means there's more time to mine a block which means we can have more participation without risking problematic congestion
 // Too low and the network could be congested
 // Too high and there's too much time for fast miners to dominate
+// A farm with an `activity_target` instead lets each block's interval adapt to how busy the previous one was
 pub const BLOCK_INTERVAL: u64 = 60 * 5; // 5 minutes, in seconds
 pub const BLOCK_REWARD: i128 = 501_0000000 * BLOCK_INTERVAL as i128 / 60; // base_per_minute * second_interval / seconds_per_minute
 pub const BLOCKS_PER_MONTH: u32 = 24 * 60 / 5 * 30; // 24 hours * 60 minutes / 5 minutes * 30 days
 pub const BLOCK_SCALE: i128 = 100_0000000000; // 100% // 10 additional zeros because our max block reward is BLOCK_REWARD (10 digits)
 pub const DECAY_RATE: i128 = 5_0000000000; // 5%
 pub const INVERSE_DECAY_RATE: i128 = BLOCK_SCALE - DECAY_RATE; // 95%
+pub const DECAY_STEPS: u32 = 1_000; // periods compounded one at a time, the default decay has hit 0 well before, squaring takes over past it
 pub const WEEK_OF_LEDGERS: u32 = 60 * 60 * 24 / 5 * 7; // assumes 5 second ledger close times
+pub const LEDGER_SECONDS: u64 = 5; // the same nominal close time, what a ledger block is paid for
 pub const V2_GENESIS_BLOCK: u32 = 30_558; // sequence 56031551
+pub const GRACE_PERIOD: u32 = 12; // ledgers, roughly a minute for hashes that land just after a rollover, off until set through `set_config`
+pub const STREAK_CAP: u32 = 12; // consecutive worked blocks before the streak bonus maxes out, off until set through `set_config`
+pub const ZEROS_BUCKETS: u32 = 16; // zeros histogram buckets, the last one also counts every pail above it
+pub const STAKE_BUCKETS: u32 = 64; // stake histogram buckets by bit length, the last one also counts every pail above it
+pub const INTERVAL_STEP: u32 = 2_500; // most an adaptive interval can move from one block to the next, 25%
+pub const BASIS_POINTS: u32 = 10_000; // 100%
+pub const BLOCK_SHARDS: u32 = 8; // concurrent `plant` and `work` calls only collide when their farmers land in the same shard
+pub const TEAM_SIZE: u32 = 10; // max members, each one is a separate mint during a team `harvest`
+pub const HISTORY_PAGE: u32 = 50; // most summaries a single `get_block_history` call reads
+pub const PAILS_PAGE: u32 = 50; // most pails a single `pending_pails` call returns
+
+/// The `KALE` constants, for farms deployed without a config
+pub fn default_farm_config(env: &Env) -> FarmConfig {
+    FarmConfig {
+        block_interval: BLOCK_INTERVAL,
+        block_reward: BLOCK_REWARD,
+        decay_period: BLOCKS_PER_MONTH,
+        decay_rate: DECAY_RATE,
+        genesis_block: V2_GENESIS_BLOCK,
+        grace_period: 0,
+        streak_cap: 0,
+        gap_weight: BASIS_POINTS,
+        stake_weight: BASIS_POINTS,
+        zeros_weight: BASIS_POINTS,
+        stake_curve: Curve::Linear,
+        zeros_curve: Curve::Linear,
+        gap_cap: 0,
+        stake_cap: StakeCap::None,
+        zeros_target: 0,
+        zeros_floor_min: 0,
+        zeros_floor_max: 64, // every nibble of a hash
+        activity_target: 0,
+        interval_min: BLOCK_INTERVAL,
+        interval_max: BLOCK_INTERVAL,
+        block_ledgers: 0,
+        eras: Vec::new(env),
+        lottery_share: 0,
+        jackpot_share: 0,
+    }
+}
 
 // TODO add more comments
 
@@ -27,7 +75,7 @@ pub const V2_GENESIS_BLOCK: u32 = 30_558; // sequence 56031551
 pub struct Contract;
 
 pub trait HomesteadTrait {
-    fn __constructor(env: Env, farmer: Address, asset: Address);
+    fn __constructor(env: Env, farmer: Address, asset: Address, config: Option<FarmConfig>);
 
     fn upgrade(env: Env, hash: BytesN<32>);
 
@@ -36,6 +84,10 @@ pub trait HomesteadTrait {
     fn unpause(env: Env);
 
     fn remove_block(env: Env, index: u32);
+
+    fn set_treasury(env: Env, treasury: Address, fee: u32);
+
+    fn set_config(env: Env, config: FarmConfig);
 }
 
 pub trait FarmTrait {
@@ -47,4 +99,44 @@ pub trait FarmTrait {
 
     fn harvest(env: Env, farmer: Address, index: u32) -> i128;
     // TODO maybe instead of a plant_v2 we just make a harvest_v2 and allow folks to harvest into another account
+
+    fn sweep_expired(env: Env, index: u32) -> i128;
+}
+
+pub trait BountyTrait {
+    fn post_bounty(env: Env, farmer: Address, index: u32, amount: i128, zeros: u32);
+
+    fn commit_bounty(env: Env, worker: Address, farmer: Address, commitment: BytesN<32>);
+
+    fn work_bounty(
+        env: Env,
+        worker: Address,
+        farmer: Address,
+        hash: BytesN<32>,
+        nonce: u64,
+    ) -> i128;
+
+    fn cancel_bounty(env: Env, farmer: Address, index: u32) -> i128;
+}
+
+pub trait TeamTrait {
+    fn create_team(env: Env, team: Address);
+
+    fn join_team(env: Env, farmer: Address, team: Address);
+
+    fn leave_team(env: Env, farmer: Address);
+
+    fn plant_team(env: Env, farmer: Address, amount: i128);
+}
+
+pub trait ViewTrait {
+    fn farmer_stats(env: Env, farmer: Address) -> FarmerStats;
+
+    fn block(env: Env, index: u32) -> Block;
+
+    fn get_block_history(env: Env, from: u32, limit: u32) -> Vec<BlockSummary>;
+
+    fn pending_pails(env: Env, farmer: Address, from: u32, limit: u32) -> Vec<PendingPail>;
+
+    fn block_reward(env: Env, index: u32) -> i128;
 }
@@ -0,0 +1,79 @@
+// Integer fixed-point helpers for the scoring curves and emission schedules
+// Every curve is monotonic so a block's min/max envelope can be run through the same curve as the farmer's value
+
+pub const LOG_SCALE: i128 = 1 << 32; // log2 results carry 32 fractional bits
+pub const MAX_EXP_ZEROS: u32 = 16; // 16^16 = 2^64 keeps a block's normalized total far from overflowing
+
+/// Floor of the square root of `x`, 0 for anything below 1
+pub fn sqrt(x: i128) -> i128 {
+    if x < 1 {
+        return 0;
+    }
+
+    // Newton's method from a power of two guaranteed to be above the root
+    let mut root = 1i128 << ((128 - x.leading_zeros()).div_ceil(2));
+
+    loop {
+        let next = (root + x / root) >> 1;
+
+        if next >= root {
+            return root;
+        }
+
+        root = next;
+    }
+}
+
+/// `log2(x)` scaled by `LOG_SCALE`, 0 for anything below 1
+pub fn log2(x: i128) -> i128 {
+    if x < 1 {
+        return 0;
+    }
+
+    let integer = 127 - x.leading_zeros();
+
+    // Normalize x into [1, 2) with 62 fractional bits so squaring it can't overflow
+    let mut y: u128 = if integer <= 62 {
+        (x as u128) << (62 - integer)
+    } else {
+        (x as u128) >> (integer - 62)
+    };
+    let mut result = integer as i128 * LOG_SCALE;
+
+    // Each squaring of y doubles the remaining fraction, shifting out one more bit of the result
+    for bit in (0..32).rev() {
+        y = (y * y) >> 62;
+
+        if y >= 2 << 62 {
+            y >>= 1;
+            result |= 1 << bit;
+        }
+    }
+
+    result
+}
+
+/// `16^zeros`, the expected number of hashes to find `zeros` leading zero nibbles, saturating at `MAX_EXP_ZEROS`
+pub fn pow16(zeros: u32) -> i128 {
+    1i128 << (4 * zeros.min(MAX_EXP_ZEROS))
+}
+
+/// `base^exp` where `base` is a fraction of `scale`, squared and multiplied in O(log exp) steps rather than `exp` of them
+///
+/// `base` must be within [0, scale] so no intermediate product exceeds `scale^2`
+pub fn pow_scaled(base: i128, exp: u32, scale: i128) -> i128 {
+    let mut result = scale;
+    let mut base = base;
+    let mut exp = exp;
+
+    while exp > 0 && result > 0 {
+        if exp & 1 == 1 {
+            result = result * base / scale;
+        }
+
+        base = base * base / scale;
+        exp >>= 1;
+    }
+
+    result
+}
@@ -1,9 +1,14 @@
-use soroban_sdk::{panic_with_error, Address, Env};
+use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
 
 use crate::{
+    contract_farm::{calculate_block_reward, new_farm_block, new_shard},
+    default_farm_config,
     errors::Errors,
-    types::{Block, Pail, Storage},
-    WEEK_OF_LEDGERS,
+    types::{
+        Block, BlockSummary, Bounty, BountyCommit, FarmConfig, FarmerStats, LegacyBlock,
+        LegacyPail, Pail, Shard, Storage, Treasury,
+    },
+    BASIS_POINTS, WEEK_OF_LEDGERS,
 };
 
 pub fn extend_instance_ttl(env: &Env) {
@@ -14,6 +19,14 @@ pub fn extend_instance_ttl(env: &Env) {
         .extend_ttl(max_ttl - WEEK_OF_LEDGERS, max_ttl);
 }
 
+pub fn extend_persistent_ttl(env: &Env, key: &Storage) {
+    let max_ttl = env.storage().max_ttl();
+
+    env.storage()
+        .persistent()
+        .extend_ttl(key, max_ttl - WEEK_OF_LEDGERS, max_ttl);
+}
+
 pub fn has_farm_homesteader(env: &Env) -> bool {
     env.storage()
         .instance()
@@ -57,15 +70,71 @@ pub fn bump_farm_index(env: &Env, current_farm_index: &mut u32) {
         .set::<Storage, u32>(&Storage::FarmIndex, &current_farm_index);
 }
 
-pub fn get_farm_block(env: &Env) -> Option<Block> {
+// The instance's `LegacyBlock` folds into the next block like one more shard, one that never handed out tickets
+pub fn get_farm_block(env: &Env) -> Option<Shard> {
+    env.storage()
+        .instance()
+        .get::<Storage, LegacyBlock>(&Storage::FarmBlock)
+        .map(|block| Shard {
+            min_gap: block.min_gap,
+            min_stake: block.min_stake,
+            min_zeros: block.min_zeros,
+            max_gap: block.max_gap,
+            max_stake: block.max_stake,
+            max_zeros: block.max_zeros,
+            entropy: block.entropy,
+            staked_total: block.staked_total,
+            normalized_total: block.normalized_total,
+            ..new_shard(env)
+        })
+}
+pub fn remove_farm_block(env: &Env) {
+    env.storage()
+        .instance()
+        .remove::<Storage>(&Storage::FarmBlock);
+}
+
+// `None` for farms that never stored a `LegacyBlock`, until the first rollover after the upgrade that's the current block
+pub fn get_farm_legacy(env: &Env) -> Option<u32> {
+    if env.storage().instance().has::<Storage>(&Storage::FarmBlock) {
+        return Some(get_farm_index(env));
+    }
+
+    env.storage()
+        .instance()
+        .get::<Storage, u32>(&Storage::FarmLegacy)
+}
+pub fn set_farm_legacy(env: &Env, index: u32) {
+    env.storage()
+        .instance()
+        .set::<Storage, u32>(&Storage::FarmLegacy, &index);
+}
+pub fn is_legacy(env: &Env, index: u32) -> bool {
+    get_farm_legacy(env).is_some_and(|legacy| index <= legacy)
+}
+
+pub fn get_farm_rollover(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get::<Storage, u32>(&Storage::FarmRollover)
+        .unwrap_or(0)
+}
+pub fn set_farm_rollover(env: &Env, sequence: u32) {
+    env.storage()
+        .instance()
+        .set::<Storage, u32>(&Storage::FarmRollover, &sequence);
+}
+
+pub fn get_farm_carry(env: &Env) -> i128 {
     env.storage()
         .instance()
-        .get::<Storage, Block>(&Storage::FarmBlock)
+        .get::<Storage, i128>(&Storage::FarmCarry)
+        .unwrap_or(0)
 }
-pub fn set_farm_block(env: &Env, block: &Block) {
+pub fn set_farm_carry(env: &Env, carry: i128) {
     env.storage()
         .instance()
-        .set::<Storage, Block>(&Storage::FarmBlock, block);
+        .set::<Storage, i128>(&Storage::FarmCarry, &carry);
 }
 
 pub fn get_farm_paused(env: &Env) -> bool {
@@ -80,36 +149,366 @@ pub fn set_farm_paused(env: &Env, paused: bool) {
         .set::<Storage, bool>(&Storage::FarmPaused, &paused);
 }
 
+pub fn get_farm_treasury(env: &Env) -> Option<Treasury> {
+    env.storage()
+        .instance()
+        .get::<Storage, Treasury>(&Storage::FarmTreasury)
+}
+pub fn set_farm_treasury(env: &Env, treasury: &Treasury) {
+    env.storage()
+        .instance()
+        .set::<Storage, Treasury>(&Storage::FarmTreasury, treasury);
+}
+
+// farms without a stored config (e.g. the original KALE farm) run on the compile-time constants
+pub fn get_farm_config(env: &Env) -> FarmConfig {
+    env.storage()
+        .instance()
+        .get::<Storage, FarmConfig>(&Storage::FarmConfig)
+        .unwrap_or_else(|| default_farm_config(env))
+}
+pub fn set_farm_config(env: &Env, config: &FarmConfig) {
+    env.storage()
+        .instance()
+        .set::<Storage, FarmConfig>(&Storage::FarmConfig, config);
+}
+
+// Blocks opened before the upgrade are only read back through their `LegacyBlock`, once written again they take the new key
 pub fn get_block(env: &Env, index: u32) -> Option<Block> {
     env.storage()
         .temporary()
-        .get::<Storage, Block>(&Storage::Block(index))
+        .get::<Storage, Block>(&Storage::BlockV2(index))
+        .or_else(|| {
+            if !is_legacy(env, index) {
+                return None;
+            }
+
+            env.storage()
+                .temporary()
+                .get::<Storage, LegacyBlock>(&Storage::Block(index))
+                .map(|block| upgrade_block(env, index, &block))
+        })
 }
 pub fn set_block(env: &Env, index: u32, block: &Block) {
     env.storage()
         .temporary()
-        .set::<Storage, Block>(&Storage::Block(index), block);
+        .set::<Storage, Block>(&Storage::BlockV2(index), block);
+}
+pub fn remove_block(env: &Env, index: u32) {
+    env.storage()
+        .temporary()
+        .remove::<Storage>(&Storage::BlockV2(index));
+
+    if is_legacy(env, index) {
+        env.storage()
+            .temporary()
+            .remove::<Storage>(&Storage::Block(index));
+    }
+}
+
+// A `LegacyBlock` settles exactly as it would have before the upgrade, equal weights, linear curves and the config's own interval
+fn upgrade_block(env: &Env, index: u32, block: &LegacyBlock) -> Block {
+    let config = get_farm_config(env);
+
+    Block {
+        timestamp: block.timestamp,
+        min_gap: block.min_gap,
+        min_stake: block.min_stake,
+        min_zeros: block.min_zeros,
+        max_gap: block.max_gap,
+        max_stake: block.max_stake,
+        max_zeros: block.max_zeros,
+        entropy: block.entropy.clone(),
+        staked_total: block.staked_total,
+        normalized_total: block.normalized_total,
+        gap_weight: BASIS_POINTS,
+        stake_weight: BASIS_POINTS,
+        zeros_weight: BASIS_POINTS,
+        interval: config.block_interval,
+        block_reward: calculate_block_reward(env, &config, index, config.block_interval),
+        ..new_farm_block(env)
+    }
+}
+
+pub fn get_block_summary(env: &Env, index: u32) -> Option<BlockSummary> {
+    env.storage()
+        .persistent()
+        .get::<Storage, BlockSummary>(&Storage::BlockSummary(index))
+}
+pub fn set_block_summary(env: &Env, index: u32, summary: &BlockSummary) {
+    let summary_key = Storage::BlockSummary(index);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, BlockSummary>(&summary_key, summary);
+
+    extend_persistent_ttl(env, &summary_key);
+}
+
+pub fn get_unclaimed(env: &Env, index: u32) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<Storage, i128>(&Storage::Unclaimed(index))
+}
+pub fn set_unclaimed(env: &Env, index: u32, unclaimed: i128) {
+    let unclaimed_key = Storage::Unclaimed(index);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, i128>(&unclaimed_key, &unclaimed);
+
+    extend_persistent_ttl(env, &unclaimed_key);
+}
+pub fn remove_unclaimed(env: &Env, index: u32) {
+    env.storage()
+        .persistent()
+        .remove::<Storage>(&Storage::Unclaimed(index));
+}
+
+pub fn get_claimed(env: &Env, index: u32, shard: u32) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<Storage, i128>(&Storage::Claimed(index, shard))
+}
+pub fn set_claimed(env: &Env, index: u32, shard: u32, claimed: i128) {
+    let claimed_key = Storage::Claimed(index, shard);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, i128>(&claimed_key, &claimed);
+
+    extend_persistent_ttl(env, &claimed_key);
+}
+pub fn remove_claimed(env: &Env, index: u32, shard: u32) {
+    env.storage()
+        .persistent()
+        .remove::<Storage>(&Storage::Claimed(index, shard));
+}
+
+pub fn get_shard(env: &Env, index: u32, shard: u32) -> Option<Shard> {
+    env.storage()
+        .temporary()
+        .get::<Storage, Shard>(&Storage::Shard(index, shard))
+}
+pub fn set_shard(env: &Env, index: u32, shard: u32, totals: &Shard) {
+    env.storage()
+        .temporary()
+        .set::<Storage, Shard>(&Storage::Shard(index, shard), totals);
 }
 
 pub fn has_pail(env: &Env, farmer: Address, index: u32) -> bool {
-    let pail_key = Storage::Pail(farmer, index);
+    let pail_key = Storage::PailV2(farmer.clone(), index);
 
     env.storage().temporary().has::<Storage>(&pail_key)
+        || (is_legacy(env, index)
+            && env
+                .storage()
+                .temporary()
+                .has::<Storage>(&Storage::Pail(farmer, index)))
 }
+// Pails planted before the upgrade are only read back through their `LegacyPail`, once written again they take the new key
 pub fn get_pail(env: &Env, farmer: Address, index: u32) -> Option<Pail> {
-    let pail_key = Storage::Pail(farmer, index);
+    let pail_key = Storage::PailV2(farmer.clone(), index);
+
+    env.storage()
+        .temporary()
+        .get::<Storage, Pail>(&pail_key)
+        .or_else(|| {
+            if !is_legacy(env, index) {
+                return None;
+            }
 
-    env.storage().temporary().get::<Storage, Pail>(&pail_key)
+            env.storage()
+                .temporary()
+                .get::<Storage, LegacyPail>(&Storage::Pail(farmer, index))
+                .map(|pail| Pail {
+                    sequence: pail.sequence,
+                    gap: pail.gap,
+                    stake: pail.stake,
+                    zeros: pail.zeros,
+                    streak: 0,
+                    ticket: None,
+                })
+        })
 }
 pub fn set_pail(env: &Env, farmer: Address, index: u32, pail: Pail) {
-    let pail_key = Storage::Pail(farmer, index);
+    let pail_key = Storage::PailV2(farmer, index);
 
     env.storage()
         .temporary()
         .set::<Storage, Pail>(&pail_key, &pail);
 }
 pub fn remove_pail(env: &Env, farmer: Address, index: u32) {
-    let pail_key = Storage::Pail(farmer, index);
+    let pail_key = Storage::PailV2(farmer.clone(), index);
 
     env.storage().temporary().remove::<Storage>(&pail_key);
+
+    if is_legacy(env, index) {
+        env.storage()
+            .temporary()
+            .remove::<Storage>(&Storage::Pail(farmer, index));
+    }
+}
+
+pub fn get_farmer_pails(env: &Env, farmer: Address) -> Vec<u32> {
+    let pails_key = Storage::FarmerPails(farmer);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, Vec<u32>>(&pails_key)
+        .unwrap_or(Vec::new(env))
+}
+pub fn set_farmer_pails(env: &Env, farmer: Address, indexes: &Vec<u32>) {
+    let pails_key = Storage::FarmerPails(farmer);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, Vec<u32>>(&pails_key, indexes);
+
+    extend_persistent_ttl(env, &pails_key);
+}
+
+pub fn get_farmer_stats(env: &Env, farmer: Address) -> FarmerStats {
+    let stats_key = Storage::FarmerStats(farmer);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, FarmerStats>(&stats_key)
+        .unwrap_or_default()
+}
+pub fn set_farmer_stats(env: &Env, farmer: Address, stats: &FarmerStats) {
+    let stats_key = Storage::FarmerStats(farmer);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, FarmerStats>(&stats_key, stats);
+
+    extend_persistent_ttl(env, &stats_key);
+}
+
+pub fn has_bounty(env: &Env, farmer: Address, index: u32) -> bool {
+    let bounty_key = Storage::Bounty(farmer, index);
+
+    env.storage().persistent().has::<Storage>(&bounty_key)
+}
+pub fn get_bounty(env: &Env, farmer: Address, index: u32) -> Option<Bounty> {
+    let bounty_key = Storage::Bounty(farmer, index);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, Bounty>(&bounty_key)
+}
+pub fn set_bounty(env: &Env, farmer: Address, index: u32, bounty: Bounty) {
+    let bounty_key = Storage::Bounty(farmer, index);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, Bounty>(&bounty_key, &bounty);
+
+    extend_persistent_ttl(env, &bounty_key);
+}
+pub fn remove_bounty(env: &Env, farmer: Address, index: u32) {
+    let bounty_key = Storage::Bounty(farmer, index);
+
+    env.storage().persistent().remove::<Storage>(&bounty_key);
+}
+
+pub fn get_bounty_commit(
+    env: &Env,
+    worker: Address,
+    farmer: Address,
+    index: u32,
+) -> Option<BountyCommit> {
+    let commit_key = Storage::BountyCommit(worker, farmer, index);
+
+    env.storage()
+        .temporary()
+        .get::<Storage, BountyCommit>(&commit_key)
+}
+pub fn set_bounty_commit(
+    env: &Env,
+    worker: Address,
+    farmer: Address,
+    index: u32,
+    commit: BountyCommit,
+) {
+    let commit_key = Storage::BountyCommit(worker, farmer, index);
+
+    env.storage()
+        .temporary()
+        .set::<Storage, BountyCommit>(&commit_key, &commit);
+}
+pub fn remove_bounty_commit(env: &Env, worker: Address, farmer: Address, index: u32) {
+    let commit_key = Storage::BountyCommit(worker, farmer, index);
+
+    env.storage().temporary().remove::<Storage>(&commit_key);
+}
+
+pub fn has_team(env: &Env, team: Address) -> bool {
+    let team_key = Storage::Team(team);
+
+    env.storage().persistent().has::<Storage>(&team_key)
+}
+pub fn get_team(env: &Env, team: Address) -> Option<Vec<Address>> {
+    let team_key = Storage::Team(team);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, Vec<Address>>(&team_key)
+}
+pub fn set_team(env: &Env, team: Address, members: &Vec<Address>) {
+    let team_key = Storage::Team(team);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, Vec<Address>>(&team_key, members);
+
+    extend_persistent_ttl(env, &team_key);
+}
+
+pub fn get_team_member(env: &Env, farmer: Address) -> Option<Address> {
+    let member_key = Storage::TeamMember(farmer);
+
+    env.storage()
+        .persistent()
+        .get::<Storage, Address>(&member_key)
+}
+pub fn set_team_member(env: &Env, farmer: Address, team: &Address) {
+    let member_key = Storage::TeamMember(farmer);
+
+    env.storage()
+        .persistent()
+        .set::<Storage, Address>(&member_key, team);
+
+    extend_persistent_ttl(env, &member_key);
+}
+pub fn remove_team_member(env: &Env, farmer: Address) {
+    let member_key = Storage::TeamMember(farmer);
+
+    env.storage().persistent().remove::<Storage>(&member_key);
+}
+
+pub fn has_team_pail(env: &Env, team: Address, index: u32) -> bool {
+    let team_pail_key = Storage::TeamPail(team, index);
+
+    env.storage().temporary().has::<Storage>(&team_pail_key)
+}
+pub fn get_team_pail(env: &Env, team: Address, index: u32) -> Option<Map<Address, i128>> {
+    let team_pail_key = Storage::TeamPail(team, index);
+
+    env.storage()
+        .temporary()
+        .get::<Storage, Map<Address, i128>>(&team_pail_key)
+}
+pub fn set_team_pail(env: &Env, team: Address, index: u32, stakes: &Map<Address, i128>) {
+    let team_pail_key = Storage::TeamPail(team, index);
+
+    env.storage()
+        .temporary()
+        .set::<Storage, Map<Address, i128>>(&team_pail_key, stakes);
+}
+pub fn remove_team_pail(env: &Env, team: Address, index: u32) {
+    let team_pail_key = Storage::TeamPail(team, index);
+
+    env.storage().temporary().remove::<Storage>(&team_pail_key);
 }
@@ -0,0 +1,209 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{setup_env, setup_farm},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env,
+};
+
+#[test]
+fn test_work_bounty() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+    let worker = Address::generate(&env);
+
+    let bounty = 10_0000000;
+
+    farm.asset_admin.mint(&farmer, &bounty);
+    farm.client.plant(&farmer, &0);
+
+    let index = farm.index(&env);
+
+    farm.client.post_bounty(&farmer, &index, &bounty, &2);
+
+    // The bounty is held in escrow
+    assert_eq!(farm.asset.balance(&farmer), 0);
+
+    let err = farm
+        .client
+        .try_post_bounty(&farmer, &index, &bounty, &2)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::BountyExists.into());
+
+    // Not enough zeros
+    let (nonce, hash) = farm.find(&env, &farmer, 0);
+
+    assert_ne!(hash.get(0), Some(0));
+
+    farm.client
+        .commit_bounty(&worker, &farmer, &commitment(&env, &worker, &hash));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    let err = farm
+        .client
+        .try_work_bounty(&worker, &farmer, &hash, &nonce)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::ZeroCountTooLow.into());
+
+    let (nonce, hash) = farm.find(&env, &farmer, 2);
+
+    // The hash can't be revealed in the same ledger it was committed to
+    farm.client
+        .commit_bounty(&worker, &farmer, &commitment(&env, &worker, &hash));
+
+    let err = farm
+        .client
+        .try_work_bounty(&worker, &farmer, &hash, &nonce)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::BountyCommitMissing.into());
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    assert_eq!(
+        farm.client.work_bounty(&worker, &farmer, &hash, &nonce),
+        bounty
+    );
+    assert_eq!(farm.asset.balance(&worker), bounty);
+
+    // The outsourced work counts towards the farmer's pail
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    // Wait out the grace period for late `work` on the closed block
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    assert!(farm.client.harvest(&farmer, &index) > 0);
+
+    let err = farm
+        .client
+        .try_cancel_bounty(&farmer, &index)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::BountyMissing.into());
+}
+
+#[test]
+fn test_cancel_bounty() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+    let bounty = 10_0000000;
+
+    farm.asset_admin.mint(&farmer, &bounty);
+    farm.client.plant(&farmer, &0);
+
+    let index = farm.index(&env);
+
+    farm.client.post_bounty(&farmer, &index, &bounty, &8);
+
+    // Can't pull the bounty out from under workers while the block is open
+    let err = farm
+        .client
+        .try_cancel_bounty(&farmer, &index)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::BountyNotExpired.into());
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&farmer, &0);
+
+    // The escrow is still there long after the block and its pails have been evicted
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 17280 + 1);
+
+    assert_eq!(farm.client.cancel_bounty(&farmer, &index), bounty);
+    assert_eq!(farm.asset.balance(&farmer), bounty);
+}
+
+#[test]
+fn test_work_bounty_replay() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+    let worker = Address::generate(&env);
+    let thief = Address::generate(&env);
+
+    let bounty = 10_0000000;
+
+    farm.asset_admin.mint(&farmer, &bounty);
+    farm.client.plant(&farmer, &0);
+
+    let index = farm.index(&env);
+
+    farm.client.post_bounty(&farmer, &index, &bounty, &2);
+
+    let (nonce, hash) = farm.find(&env, &farmer, 2);
+
+    farm.client
+        .commit_bounty(&worker, &farmer, &commitment(&env, &worker, &hash));
+
+    // Copying the worker's commitment doesn't commit to the hash for anyone else
+    farm.client
+        .commit_bounty(&thief, &farmer, &commitment(&env, &worker, &hash));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    let err = farm
+        .client
+        .try_work_bounty(&thief, &farmer, &hash, &nonce)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::HashInvalid.into());
+
+    // Once revealed the hash is replayed straight into the farmer's pail ahead of the worker's claim
+    farm.client.work(&farmer, &hash, &nonce);
+
+    // but the worker committed to it first so the bounty is still theirs
+    assert_eq!(
+        farm.client.work_bounty(&worker, &farmer, &hash, &nonce),
+        bounty
+    );
+    assert_eq!(farm.asset.balance(&worker), bounty);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    let err = farm
+        .client
+        .try_cancel_bounty(&farmer, &index)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::BountyMissing.into());
+}
+
+fn commitment(env: &Env, worker: &Address, hash: &BytesN<32>) -> BytesN<32> {
+    let mut committed = Bytes::new(env);
+
+    committed.append(&worker.clone().to_xdr(env));
+    committed.append(&hash.clone().into());
+
+    env.crypto().keccak256(&committed).to_bytes()
+}
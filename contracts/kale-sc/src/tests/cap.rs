@@ -0,0 +1,80 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::{FarmConfig, StakeCap},
+    BASIS_POINTS, BLOCK_INTERVAL,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn capped_config(env: &Env, stake_cap: StakeCap) -> FarmConfig {
+    FarmConfig {
+        stake_cap,
+        ..farm_config(env)
+    }
+}
+
+#[test]
+fn test_stake_cap() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(capped_config(&env, StakeCap::Median(2 * BASIS_POINTS))),
+    );
+
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+    let whale = Address::generate(&env);
+
+    farm.asset_admin.mint(&farmer_1, &1_000);
+    farm.asset_admin.mint(&farmer_2, &1_000);
+    farm.asset_admin.mint(&whale, &1_000_000);
+
+    // Nothing's been planted yet so the first block is uncapped
+    farm.client.plant(&farmer_1, &100);
+    farm.client.plant(&farmer_2, &200);
+    farm.client.plant(&whale, &1_000_000);
+
+    let index = farm.index(&env);
+
+    assert_eq!(farm.client.block(&index).stake_counts.get_unchecked(7), 1);
+    assert_eq!(farm.client.block(&index).stake_counts.get_unchecked(8), 1);
+    assert_eq!(farm.client.block(&index).stake_counts.get_unchecked(20), 1);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    // The whale doesn't drag the median, 200 sits in the bucket topping out at 255 so the next block caps pails at 510
+    farm.client.plant(&farmer_1, &510);
+
+    assert_eq!(farm.block(&env, farm.index(&env)).stake_cap, 510);
+    assert_eq!(
+        farm.client.try_plant(&farmer_2, &511).unwrap_err().unwrap(),
+        Errors::PlantAmountTooHigh.into()
+    );
+
+    // A new cap only applies from the next block
+    farm.client
+        .set_config(&capped_config(&env, StakeCap::Absolute(50)));
+    farm.client.plant(&farmer_2, &300);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&farmer_1, &50);
+
+    assert_eq!(
+        farm.client.try_plant(&farmer_2, &51).unwrap_err().unwrap(),
+        Errors::PlantAmountTooHigh.into()
+    );
+
+    assert_eq!(
+        farm.client
+            .try_set_config(&capped_config(&env, StakeCap::Median(BASIS_POINTS - 1)))
+            .unwrap_err()
+            .unwrap(),
+        Errors::ConfigInvalid.into()
+    );
+}
@@ -0,0 +1,97 @@
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{
+    tests::utils::{setup_env, setup_farm},
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_carry() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let rollover = |farmer: &Address| {
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 1);
+
+        farm.client.plant(farmer, &0);
+    };
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    // Seven equal shares can't divide the pool exactly
+    let farmers: Vec<Address> = (0..7).map(|_| Address::generate(&env)).collect();
+
+    for farmer in farmers.iter() {
+        farm.client.plant(farmer, &0);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    for farmer in farmers.iter() {
+        farm.work(&env, farmer, 1);
+    }
+
+    // The next block is planted with a stake but never worked
+    let idler = Address::generate(&env);
+    let stake = 1_0000000;
+
+    farm.asset_admin.mint(&idler, &stake);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&idler, &stake);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let harvested: i128 = farmers
+        .iter()
+        .map(|farmer| farm.client.harvest(farmer, &index))
+        .sum();
+    let dust = BLOCK_REWARD - harvested;
+
+    assert_eq!(dust, BLOCK_REWARD % 7);
+
+    // Harvests only touch their shards, so the dust is swept once the block is evicted
+    farm.client.remove_block(&index);
+
+    assert_eq!(farm.client.sweep_expired(&index), dust);
+
+    // and goes to the next block opened
+    rollover(&farmers[0]);
+
+    assert_eq!(farm.block(&env, index + 2).carry, dust);
+
+    // Once the unworked block is past its grace period its whole pool follows
+    rollover(&farmers[0]);
+
+    assert_eq!(farm.block(&env, index + 3).carry, BLOCK_REWARD + stake);
+
+    // and the lone farmer of the carrying block takes it all
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    farm.work(&env, &farmers[0], 0);
+
+    rollover(&farmers[0]);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    assert_eq!(
+        farm.client.harvest(&farmers[0], &(index + 3)),
+        BLOCK_REWARD * 2 + stake
+    );
+}
@@ -0,0 +1,80 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm, setup_farm_with_config},
+    types::FarmConfig,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_custom_config() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            block_interval: 60,
+            block_reward: 100_0000000,
+            decay_period: 1,
+            decay_rate: 50_0000000000, // 50%
+            genesis_block: 0,
+            grace_period: 0,
+            streak_cap: 0,
+            interval_min: 60,
+            interval_max: 60,
+            ..farm_config(&env)
+        }),
+    );
+
+    let farmer = Address::generate(&env);
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    farm.client.plant(&farmer, &0);
+
+    let index = farm.index(&env);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer, 0);
+
+    // a minute is a full block on this farm
+    env.ledger().set_timestamp(timestamp + 60);
+
+    farm.client.plant(&farmer, &0);
+
+    assert_eq!(farm.index(&env), index + 1);
+
+    // a lone farmer takes the whole block reward, halved once for each elapsed decay period
+    let reward = farm.client.harvest(&farmer, &index);
+
+    assert_eq!(reward, 100_0000000 >> index);
+    assert_eq!(farm.asset.balance(&farmer), reward);
+}
+
+#[test]
+fn test_config_invalid() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let err = farm
+        .client
+        .try_set_config(&FarmConfig {
+            block_interval: 60,
+            block_reward: 100_0000000,
+            decay_period: 0,
+            decay_rate: 0,
+            genesis_block: 0,
+            grace_period: 0,
+            streak_cap: 0,
+            interval_min: 60,
+            interval_max: 60,
+            ..farm_config(&env)
+        })
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::ConfigInvalid.into());
+}
@@ -0,0 +1,90 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{setup_env, setup_farm, Farm},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+// Work the farmer's pail and return the zeros the farm will count for it
+fn work(env: &Env, farm: &Farm, farmer: &Address, zeros: u32) -> u32 {
+    let (nonce, hash) = farm.find(env, farmer, zeros);
+    let mut zeros = 0;
+
+    farm.client.work(farmer, &hash, &nonce);
+
+    for byte in hash {
+        if byte == 0 {
+            zeros += 2;
+        } else {
+            zeros += byte.leading_zeros() / 4;
+            break;
+        }
+    }
+
+    zeros
+}
+
+#[test]
+fn test_block_counts() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+    let farmer_3 = Address::generate(&env);
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    farm.client.plant(&farmer_1, &0);
+    farm.client.plant(&farmer_2, &0);
+    farm.client.plant(&farmer_3, &0);
+
+    let index = farm.index(&env);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    work(&env, &farm, &farmer_1, 1);
+
+    // A re-work replaces the pail's zeros rather than adding a second worker
+    let zeros_1 = work(&env, &farm, &farmer_1, 3);
+    let zeros_2 = work(&env, &farm, &farmer_2, 1);
+
+    // The open block's counts are read through its shards
+    let block = farm.client.block(&index);
+
+    assert_eq!(block.planted_count, 3);
+    assert_eq!(block.worked_count, 2);
+    assert_eq!(block.zeros_total, zeros_1 + zeros_2);
+    assert_eq!(block.harvested_count, 0);
+
+    assert_eq!(
+        farm.client.try_block(&(index + 1)).unwrap_err().unwrap(),
+        Errors::BlockMissing.into()
+    );
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&farmer_1, &0);
+
+    // Once folded the block holds the same counts itself
+    assert_eq!(farm.block(&env, index), block);
+
+    env.ledger()
+        .set_sequence_number(sequence + 2 + GRACE_PERIOD);
+
+    let reward_1 = farm.client.harvest(&farmer_1, &index);
+    let reward_2 = farm.client.harvest(&farmer_2, &index);
+
+    let block = farm.client.block(&index);
+
+    assert_eq!(block.harvested_count, 2);
+    assert_eq!(block.total_reward_minted, reward_1 + reward_2);
+
+    // Harvests are counted in the farmers' shards, the block itself isn't written again
+    assert_eq!(farm.block(&env, index).harvested_count, 0);
+}
@@ -0,0 +1,92 @@
+use crate::{
+    math::{log2, pow16, sqrt, LOG_SCALE},
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::{Curve, FarmConfig},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_math_helpers() {
+    for x in [1, 2, 15, 16, 17, 1_0000000, i64::MAX as i128, i128::MAX] {
+        let root = sqrt(x);
+
+        assert!(root * root <= x);
+        assert!((root + 1)
+            .checked_mul(root + 1)
+            .is_none_or(|square| square > x));
+    }
+
+    assert_eq!(sqrt(0), 0);
+    assert_eq!(log2(1), 0);
+    assert_eq!(log2(1 << 40), 40 * LOG_SCALE);
+    // log2(3) = 1.58496250...
+    assert!((log2(3) - 1_58496250 * LOG_SCALE / 1_00000000).abs() < LOG_SCALE / 1_00000000);
+    assert_eq!(pow16(2), 256);
+    assert_eq!(pow16(64), 1 << 64);
+}
+
+/// Return the (whale, minnow) rewards for the second of two blocks scored with `stake_curve`
+fn farm_curve(stake_curve: Curve) -> (i128, i128) {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            stake_curve,
+            ..farm_config(&env)
+        }),
+    );
+
+    let whale = Address::generate(&env);
+    let minnow = Address::generate(&env);
+    let whale_stake = 10000_0000000;
+    let minnow_stake = 100_0000000;
+
+    farm.asset_admin.mint(&whale, &(whale_stake * 2));
+    farm.asset_admin.mint(&minnow, &(minnow_stake * 2));
+
+    let mut index = 0;
+
+    for _ in 0..2 {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&whale, &whale_stake);
+        farm.client.plant(&minnow, &minnow_stake);
+        farm.client.plant(&Address::generate(&env), &0);
+
+        index = farm.index(&env);
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        farm.work(&env, &whale, 0);
+        farm.work(&env, &minnow, 0);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 2);
+    }
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    (
+        farm.client.harvest(&whale, &index),
+        farm.client.harvest(&minnow, &index),
+    )
+}
+
+#[test]
+fn test_stake_curves() {
+    let (whale_linear, minnow_linear) = farm_curve(Curve::Linear);
+    let (whale_sqrt, minnow_sqrt) = farm_curve(Curve::Sqrt);
+    let (whale_log, minnow_log) = farm_curve(Curve::Log);
+
+    // Flattening the stake curve moves reward from the whale to the minnow, log more so than sqrt
+    assert!(whale_sqrt < whale_linear && minnow_sqrt > minnow_linear);
+    assert!(whale_log < whale_sqrt && minnow_log > minnow_sqrt);
+}
@@ -0,0 +1,88 @@
+extern crate std;
+
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BASIS_POINTS, BLOCK_INTERVAL,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_zeros_floor() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            zeros_target: BASIS_POINTS / 2,
+            zeros_floor_max: 2,
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    let mut farmers = std::vec::Vec::new();
+
+    for _ in 0..4 {
+        let farmer = Address::generate(&env);
+
+        farm.client.plant(&farmer, &0);
+        farmers.push(farmer);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    // Half the farmers reach at least 2 zeros so that's where the next floor lands
+    for (i, farmer) in farmers.iter().enumerate() {
+        farm.work(&env, farmer, if i < 2 { 2 } else { 0 });
+    }
+
+    // The genesis block has nothing to go off of
+    assert_eq!(farm.block(&env, index).zeros_floor, 0);
+    assert_eq!(
+        farm.client.block(&index).zeros_counts.iter().sum::<u32>(),
+        4
+    );
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    let farmer = Address::generate(&env);
+
+    farm.client.plant(&farmer, &0);
+
+    assert_eq!(farm.block(&env, index + 1).zeros_floor, 2);
+
+    env.ledger().set_sequence_number(sequence + 2);
+
+    let (nonce, hash) = farm.find(&env, &farmer, 0);
+
+    // The first nonce's hash doesn't clear the floor
+    assert!(hash.get_unchecked(0) != 0);
+    assert_eq!(
+        farm.client
+            .try_work(&farmer, &hash, &nonce)
+            .unwrap_err()
+            .unwrap(),
+        Errors::ZeroCountTooLow.into()
+    );
+
+    farm.work(&env, &farmer, 2);
+
+    // Only one of four planters clears the floor this time so the next one eases off
+    for _ in 0..3 {
+        farm.client.plant(&Address::generate(&env), &0);
+    }
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.block(&env, index + 2).zeros_floor, 1);
+}
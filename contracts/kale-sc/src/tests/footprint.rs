@@ -0,0 +1,240 @@
+extern crate std;
+
+use std::{rc::Rc, vec::Vec};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::{
+        ContractDataDurability, ContractDataEntry, ExtensionPoint, LedgerEntry, LedgerEntryData,
+        LedgerEntryExt, LedgerKey, LedgerKeyContractData, ScAddress, ScVal, ToXdr,
+    },
+    Address, Env, IntoVal, TryFromVal, Val,
+};
+
+use crate::{
+    contract_farm::get_shard_index,
+    tests::utils::{setup_env, setup_farm, Farm},
+    types::Storage,
+    BLOCK_INTERVAL, BLOCK_SHARDS,
+};
+
+/// Rebuild the same farm from scratch (generated addresses are deterministic) with a block that has just closed
+fn at_rollover<R>(planters: usize, f: impl FnOnce(&Env, &Farm, &[Address]) -> R) -> R {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    let workers: Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+
+    for worker in workers.iter() {
+        farm.client.plant(worker, &0);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    for worker in workers.iter() {
+        farm.work(&env, worker, 1);
+    }
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    let farmers: Vec<Address> = (0..planters).map(|_| Address::generate(&env)).collect();
+
+    f(&env, &farm, &farmers)
+}
+
+/// XDR size of the block and the farmer's shard at `index`, the entries a steady `plant` reads
+fn entry_sizes(env: &Env, farm: &Farm, farmer: &Address, index: u32) -> [Option<u32>; 2] {
+    let shard = get_shard_index(env, farmer);
+
+    env.as_contract(&farm.address, || {
+        let storage = env.storage().temporary();
+
+        [Storage::BlockV2(index), Storage::Shard(index, shard)].map(|key| {
+            storage
+                .get::<_, Val>(&key)
+                .map(|entry| entry.to_xdr(env).len())
+        })
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// Every farm entry a `plant` into the block after `index` could touch, for any of the `farmers`
+fn candidate_keys(farmers: &[Address], index: u32) -> Vec<Storage> {
+    let mut keys = Vec::new();
+
+    for i in index.saturating_sub(1)..=index + 2 {
+        keys.extend([
+            Storage::Block(i),
+            Storage::BlockV2(i),
+            Storage::BlockSummary(i),
+            Storage::Unclaimed(i),
+        ]);
+        keys.extend((0..BLOCK_SHARDS).map(|shard| Storage::Shard(i, shard)));
+        keys.extend((0..BLOCK_SHARDS).map(|shard| Storage::Claimed(i, shard)));
+
+        for farmer in farmers {
+            keys.extend([
+                Storage::Pail(farmer.clone(), i),
+                Storage::PailV2(farmer.clone(), i),
+                Storage::TeamPail(farmer.clone(), i),
+            ]);
+        }
+    }
+
+    for farmer in farmers {
+        keys.extend([
+            Storage::FarmerStats(farmer.clone()),
+            Storage::FarmerPails(farmer.clone()),
+            Storage::Team(farmer.clone()),
+            Storage::TeamMember(farmer.clone()),
+        ]);
+    }
+
+    keys
+}
+
+/// Recover which of the `keys` the last call's footprint covers, and how
+///
+/// Once switched to enforcing the host keeps the footprint it recorded for that call
+/// and only lets a key in it be read, and only a read write one be written
+fn footprint(env: &Env, farm: &Farm, keys: &[Storage]) -> Vec<[Option<Access>; 2]> {
+    let host = env.host();
+    let contract = ScAddress::from(&farm.address);
+
+    // Probing through a contract frame would start a new invocation and reset the footprint
+    host.switch_to_enforcing_storage().unwrap();
+
+    keys.iter()
+        .map(|key| {
+            let key = ScVal::try_from_val(env, &IntoVal::<Env, Val>::into_val(key, env)).unwrap();
+
+            [
+                ContractDataDurability::Temporary,
+                ContractDataDurability::Persistent,
+            ]
+            .map(|durability| {
+                let ledger_key = Rc::new(LedgerKey::ContractData(LedgerKeyContractData {
+                    contract: contract.clone(),
+                    key: key.clone(),
+                    durability,
+                }));
+                let entry = Rc::new(LedgerEntry {
+                    last_modified_ledger_seq: 0,
+                    data: LedgerEntryData::ContractData(ContractDataEntry {
+                        ext: ExtensionPoint::V0,
+                        contract: contract.clone(),
+                        key: key.clone(),
+                        durability,
+                        val: ScVal::Void,
+                    }),
+                    ext: LedgerEntryExt::V0,
+                });
+
+                if host.get_ledger_entry(&ledger_key).is_err() {
+                    None
+                } else if host
+                    .add_ledger_entry(&ledger_key, &entry, Some(env.ledger().sequence()))
+                    .is_err()
+                {
+                    Some(Access::ReadOnly)
+                } else {
+                    Some(Access::ReadWrite)
+                }
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_plant_footprint_across_rollover() {
+    // More planters than shards so at least two land in the same shard
+    let planters = BLOCK_SHARDS as usize + 1;
+
+    // Every planter simulates against the same state, so every simulation takes the rollover branch
+    let simulated: Vec<_> = (0..planters)
+        .map(|i| {
+            at_rollover(planters, |env, farm, farmers| {
+                let index = farm.index(env);
+                let sizes = entry_sizes(env, farm, &farmers[i], index + 1);
+
+                let keys = candidate_keys(farmers, index);
+
+                // Nothing may touch the farm between the call and its probes, they'd record their own footprint
+                farm.client.plant(&farmers[i], &0);
+
+                let resources = env.cost_estimate().resources();
+
+                (resources, sizes, footprint(env, farm, &keys))
+            })
+        })
+        .collect();
+
+    // Only the first execution rolls the block over, the rest execute the steady branch after it
+    for (i, (simulation, simulated_sizes, simulated_footprint)) in simulated.iter().enumerate() {
+        at_rollover(planters, |env, farm, farmers| {
+            let index = farm.index(env);
+
+            for farmer in &farmers[..i] {
+                farm.client.plant(farmer, &0);
+            }
+
+            if i > 0 {
+                // Nothing the steady branch reads appeared or changed size since its simulation
+                assert_eq!(
+                    entry_sizes(env, farm, &farmers[i], index + 1),
+                    *simulated_sizes
+                );
+            }
+
+            let keys = candidate_keys(farmers, index);
+
+            farm.client.plant(&farmers[i], &0);
+
+            let execution = env.cost_estimate().resources();
+            let executed_footprint = footprint(env, farm, &keys);
+
+            assert!(
+                execution.memory_read_entries + execution.disk_read_entries
+                    <= simulation.memory_read_entries + simulation.disk_read_entries
+            );
+            assert!(execution.disk_read_bytes <= simulation.disk_read_bytes);
+            assert!(execution.write_entries <= simulation.write_entries);
+            assert!(execution.write_bytes <= simulation.write_bytes);
+
+            // Every key the execution reads was read by its simulation, and every key it writes was written
+            for ((key, executed), simulated) in keys
+                .iter()
+                .zip(executed_footprint.iter())
+                .zip(simulated_footprint.iter())
+            {
+                for (executed, simulated) in executed.iter().zip(simulated.iter()) {
+                    assert!(executed <= simulated, "{key:?} {executed:?} {simulated:?}");
+                }
+            }
+
+            let position = |key: Storage| keys.iter().position(|k| *k == key).unwrap();
+            let closed = position(Storage::BlockV2(index));
+            let pail = position(Storage::PailV2(farmers[i].clone(), index + 1));
+
+            // Every simulation closed the block, only the first execution did and the rest never touched it
+            assert_eq!(simulated_footprint[closed][0], Some(Access::ReadWrite));
+            assert_eq!(
+                executed_footprint[closed][0],
+                (i == 0).then_some(Access::ReadWrite)
+            );
+
+            // and each planted into the next block
+            assert_eq!(simulated_footprint[pail][0], Some(Access::ReadWrite));
+            assert_eq!(executed_footprint[pail][0], Some(Access::ReadWrite));
+        });
+    }
+}
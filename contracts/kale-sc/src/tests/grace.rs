@@ -0,0 +1,83 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, find_nonce_and_hash, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_late_work() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            grace_period: GRACE_PERIOD,
+            ..farm_config(&env)
+        }),
+    );
+
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    farm.client.plant(&farmer_1, &0);
+    farm.client.plant(&farmer_2, &0);
+
+    let index = farm.index(&env);
+    let block = farm.block(&env, index);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer_1, 1);
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.index(&env), index + 1);
+
+    // farmer 2's hash lands just after the rollover but still counts for the closed block
+    env.ledger().set_sequence_number(sequence + 3);
+
+    let (nonce, hash) = find_nonce_and_hash(&env, &index, &block.entropy, &farmer_2, 1);
+
+    farm.client.work(&farmer_2, &hash, &nonce);
+
+    // Nobody can harvest the closed block while its totals can still move
+    let err = farm
+        .client
+        .try_harvest(&farmer_1, &index)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::HarvestNotReady.into());
+
+    env.ledger()
+        .set_sequence_number(sequence + 2 + GRACE_PERIOD);
+
+    // Once the grace period is over the previous block is closed for good
+    let (nonce, hash) = find_nonce_and_hash(&env, &index, &block.entropy, &farmer_2, 2);
+    let err = farm
+        .client
+        .try_work(&farmer_2, &hash, &nonce)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::PailMissing.into());
+
+    let reward_1 = farm.client.harvest(&farmer_1, &index);
+    let reward_2 = farm.client.harvest(&farmer_2, &index);
+
+    // The late work was included in the totals both harvests were measured against
+    assert!(reward_1 > 0);
+    assert!(reward_2 > 0);
+    assert!(reward_1 + reward_2 <= BLOCK_REWARD);
+    assert!(reward_1 + reward_2 >= BLOCK_REWARD - 2);
+}
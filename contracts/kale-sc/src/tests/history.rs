@@ -0,0 +1,99 @@
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{
+    tests::utils::{setup_env, setup_farm},
+    BLOCK_INTERVAL, BLOCK_REWARD, HISTORY_PAGE,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_block_history() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+    let stake = 1_0000000;
+
+    farm.asset_admin.mint(&farmer, &(stake * 3));
+
+    let mut blocks = Vec::new();
+
+    for _ in 0..3 {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&farmer, &stake);
+        farm.client.plant(&Address::generate(&env), &0);
+
+        let index = farm.index(&env);
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        farm.work(&env, &farmer, 1);
+
+        blocks.push((index, farm.block(&env, index)));
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 2);
+    }
+
+    farm.client.plant(&farmer, &0);
+
+    // The summaries outlive the temporary blocks
+    for (index, _) in blocks.iter() {
+        farm.client.remove_block(index);
+    }
+
+    let history = farm.client.get_block_history(&0, &10);
+
+    assert_eq!(history.len(), 3);
+
+    for ((index, block), summary) in blocks.iter().zip(history.iter()) {
+        assert_eq!(summary.index, *index);
+        assert_eq!(summary.timestamp, block.timestamp);
+        assert_eq!(summary.entropy, block.entropy);
+        assert_eq!(summary.reward, BLOCK_REWARD);
+        assert_eq!(summary.participants, 1);
+        // The only stake was reclaimed by `work`
+        assert_eq!(summary.staked_total, 0);
+        assert!(summary.normalized_total > 0);
+    }
+
+    let page = farm.client.get_block_history(&1, &1);
+
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0), history.get_unchecked(1));
+
+    // The open block hasn't been summarized yet
+    assert_eq!(farm.client.get_block_history(&3, &10).len(), 0);
+}
+
+#[test]
+fn test_block_history_page() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+
+    for _ in 0..=HISTORY_PAGE {
+        farm.client.plant(&farmer, &0);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 1);
+    }
+
+    farm.client.plant(&farmer, &0);
+
+    // However many are asked for, a page never reads more than `HISTORY_PAGE` summaries
+    assert_eq!(
+        farm.client.get_block_history(&0, &u32::MAX).len(),
+        HISTORY_PAGE
+    );
+}
@@ -0,0 +1,90 @@
+extern crate std;
+
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BASIS_POINTS, BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_adaptive_interval() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            activity_target: BASIS_POINTS, // a plant or work every ledger
+            interval_min: 60,
+            interval_max: BLOCK_INTERVAL * 2,
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+    let index = farm.index(&env);
+
+    // A busy block, 4 plants and 4 works in 2 ledgers
+    let mut farmers = std::vec::Vec::new();
+
+    for _ in 0..4 {
+        let farmer = Address::generate(&env);
+
+        farm.client.plant(&farmer, &0);
+        farmers.push(farmer);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    for farmer in farmers.iter() {
+        farm.work(&env, farmer, 0);
+    }
+
+    env.ledger().set_sequence_number(sequence + 2);
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+
+    let farmer = &farmers[0];
+
+    farm.client.plant(farmer, &0);
+
+    // The next block stays open longer, by at most a single step
+    let interval = BLOCK_INTERVAL * 5 / 4;
+
+    assert_eq!(farm.block(&env, index).interval, BLOCK_INTERVAL);
+    assert_eq!(farm.block(&env, index + 1).interval, interval);
+
+    env.ledger().set_sequence_number(sequence + 3);
+
+    farm.work(&env, farmer, 0);
+
+    // Past the fixed interval but not the adapted one
+    env.ledger().set_sequence_number(sequence + 100);
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL * 2);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.index(&env), index + 1);
+
+    // A quiet block closes sooner
+    env.ledger()
+        .set_timestamp(timestamp + BLOCK_INTERVAL + interval);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(
+        farm.block(&env, index + 2).interval,
+        interval - interval / 4
+    );
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    // The lone farmer's reward covers the longer block
+    assert_eq!(
+        farm.client.harvest(farmer, &(index + 1)),
+        BLOCK_REWARD * interval as i128 / BLOCK_INTERVAL as i128
+    );
+}
@@ -0,0 +1,66 @@
+extern crate std;
+
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BASIS_POINTS, BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_jackpot() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            jackpot_share: BASIS_POINTS / 10, // 10%
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    let small = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    farm.client.plant(&small, &0);
+    farm.client.plant(&first, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.client.plant(&second, &0);
+    farm.work(&env, &small, 0);
+
+    // The same zeros and gap, but the first to submit keeps the trophy
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.work(&env, &first, 2);
+
+    env.ledger().set_sequence_number(sequence + 3);
+
+    farm.work(&env, &second, 2);
+
+    let block = farm.client.block(&index);
+
+    assert_eq!(block.best_zeros, 2);
+    assert_eq!(block.best_sequence, sequence + 2);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 4);
+
+    farm.client.plant(&small, &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward_first = farm.client.harvest(&first, &index);
+    let reward_second = farm.client.harvest(&second, &index);
+
+    assert_eq!(reward_first - reward_second, BLOCK_REWARD / 10);
+}
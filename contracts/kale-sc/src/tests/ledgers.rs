@@ -0,0 +1,58 @@
+extern crate std;
+
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_ledger_blocks() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            block_ledgers: 12, // a minute of 5 second ledgers
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+    let index = farm.index(&env);
+    let farmer = Address::generate(&env);
+
+    farm.client.plant(&farmer, &0);
+
+    assert_eq!(farm.block(&env, index).ledgers, 12);
+
+    // Slow ledger closes don't end the block early
+    env.ledger().set_sequence_number(sequence + 11);
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL * 10);
+
+    farm.work(&env, &farmer, 0);
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.index(&env), index);
+
+    // but the 12th ledger does, however little time has passed
+    env.ledger().set_sequence_number(sequence + 12);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.index(&env), index + 1);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    // A minute of ledgers pays a minute of the block reward
+    assert_eq!(farm.block(&env, index).interval, 60);
+    assert_eq!(
+        farm.client.harvest(&farmer, &index),
+        BLOCK_REWARD * 60 / BLOCK_INTERVAL as i128
+    );
+}
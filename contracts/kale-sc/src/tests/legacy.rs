@@ -0,0 +1,214 @@
+use crate::{
+    tests::utils::{find_nonce_and_hash, setup_env, setup_farm},
+    types::{LegacyBlock, LegacyPail, Storage},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN,
+};
+
+#[test]
+fn test_legacy_block_rollover() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let index = 7;
+    let timestamp = env.ledger().timestamp();
+    let sequence = env.ledger().sequence();
+
+    // A farm upgraded mid-block, its instance and current block still have the original shape
+    let farm_block = LegacyBlock {
+        timestamp,
+        min_gap: u32::MAX,
+        min_stake: i128::MAX,
+        min_zeros: u32::MAX,
+        max_gap: u32::MIN,
+        max_stake: i128::MIN,
+        max_zeros: u32::MIN,
+        entropy: BytesN::from_array(&env, &[1; 32]),
+        staked_total: 0,
+        normalized_total: 0,
+    };
+    let block = LegacyBlock {
+        min_gap: 1,
+        min_stake: 0,
+        min_zeros: 1,
+        max_gap: 10,
+        max_stake: 0,
+        max_zeros: 8,
+        entropy: BytesN::from_array(&env, &[2; 32]),
+        ..farm_block.clone()
+    };
+
+    env.as_contract(&farm.address, || {
+        env.storage()
+            .instance()
+            .set::<Storage, u32>(&Storage::FarmIndex, &index);
+        env.storage()
+            .instance()
+            .set::<Storage, LegacyBlock>(&Storage::FarmBlock, &farm_block);
+        env.storage()
+            .temporary()
+            .set::<Storage, LegacyBlock>(&Storage::Block(index), &block);
+    });
+
+    // The legacy block still takes plants and work until it closes
+    let farmer = Address::generate(&env);
+
+    farm.client.plant(&farmer, &0);
+
+    assert_eq!(farm.index(&env), index);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    let (nonce, hash) = find_nonce_and_hash(&env, &index, &block.entropy, &farmer, 1);
+
+    farm.client.work(&farmer, &hash, &nonce);
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    assert_eq!(farm.index(&env), index + 1);
+
+    // Folding the instance's `LegacyBlock` retires it and re-keys the closed block
+    let (has_farm_block, legacy) = env.as_contract(&farm.address, || {
+        (
+            env.storage().instance().has(&Storage::FarmBlock),
+            env.storage()
+                .instance()
+                .get::<Storage, u32>(&Storage::FarmLegacy),
+        )
+    });
+
+    assert!(!has_farm_block);
+    assert_eq!(legacy, Some(index));
+
+    let closed = farm.block(&env, index);
+
+    assert_eq!(closed.timestamp, timestamp);
+    assert_eq!(closed.max_zeros, 8);
+    assert_eq!(closed.worked_count, 1);
+
+    // and its lone worker harvests the whole reward
+    env.ledger()
+        .set_sequence_number(sequence + 2 + GRACE_PERIOD);
+
+    assert_eq!(
+        farm.client.harvest(&farmer, &index),
+        farm.client.block_reward(&index)
+    );
+}
+
+#[test]
+fn test_legacy_pails() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let index = 7;
+    let timestamp = env.ledger().timestamp();
+    // Well past the closed block's grace period
+    let sequence = env.ledger().sequence() + GRACE_PERIOD;
+
+    env.ledger().set_sequence_number(sequence);
+
+    // Every range is flat so each worked pail scores the same
+    let block = LegacyBlock {
+        timestamp,
+        min_gap: 5,
+        min_stake: 0,
+        min_zeros: 1,
+        max_gap: 5,
+        max_stake: 0,
+        max_zeros: 1,
+        entropy: BytesN::from_array(&env, &[2; 32]),
+        staked_total: 0,
+        normalized_total: 3,
+    };
+
+    // One farmer worked the closed block before the upgrade, the other only planted the current one
+    let harvester = Address::generate(&env);
+    let worker = Address::generate(&env);
+
+    // The current block hasn't been worked yet
+    let current = LegacyBlock {
+        normalized_total: 0,
+        ..block.clone()
+    };
+
+    env.as_contract(&farm.address, || {
+        env.storage()
+            .instance()
+            .set::<Storage, u32>(&Storage::FarmIndex, &index);
+        env.storage()
+            .instance()
+            .set::<Storage, LegacyBlock>(&Storage::FarmBlock, &current);
+        env.storage()
+            .temporary()
+            .set::<Storage, LegacyBlock>(&Storage::Block(index - 1), &block);
+        env.storage()
+            .temporary()
+            .set::<Storage, LegacyBlock>(&Storage::Block(index), &current);
+        env.storage().temporary().set::<Storage, LegacyPail>(
+            &Storage::Pail(harvester.clone(), index - 1),
+            &LegacyPail {
+                sequence,
+                gap: Some(5),
+                stake: 0,
+                zeros: Some(1),
+            },
+        );
+        env.storage().temporary().set::<Storage, LegacyPail>(
+            &Storage::Pail(worker.clone(), index),
+            &LegacyPail {
+                sequence,
+                gap: None,
+                stake: 0,
+                zeros: None,
+            },
+        );
+    });
+
+    assert_eq!(
+        farm.client.harvest(&harvester, &(index - 1)),
+        farm.client.block_reward(&(index - 1))
+    );
+
+    // A legacy pail can't be planted over but can still be worked
+    assert!(farm.client.try_plant(&worker, &0).is_err());
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    let (nonce, hash) = find_nonce_and_hash(&env, &index, &block.entropy, &worker, 1);
+
+    assert_eq!(farm.client.work(&worker, &hash, &nonce), 1);
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(sequence + 2 + GRACE_PERIOD);
+
+    assert_eq!(
+        farm.client.harvest(&worker, &index),
+        farm.client.block_reward(&index)
+    );
+
+    // Harvesting removed both pails, whichever key they were under
+    env.as_contract(&farm.address, || {
+        for (farmer, index) in [(&harvester, index - 1), (&worker, index)] {
+            assert!(!env
+                .storage()
+                .temporary()
+                .has(&Storage::Pail(farmer.clone(), index)));
+            assert!(!env
+                .storage()
+                .temporary()
+                .has(&Storage::PailV2(farmer.clone(), index)));
+        }
+    });
+}
@@ -0,0 +1,67 @@
+extern crate std;
+
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BASIS_POINTS, BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_lottery() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            lottery_share: BASIS_POINTS / 10, // 10%
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    let farmers: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+
+    for farmer in farmers.iter() {
+        farm.client.plant(farmer, &0);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    for farmer in farmers.iter() {
+        farm.work(&env, farmer, 1);
+    }
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&farmers[0], &0);
+
+    assert_ne!(farm.block(&env, index).lottery_ticket, u32::MAX);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let prize = BLOCK_REWARD / 10;
+    let share = (BLOCK_REWARD - prize) / 5;
+
+    let rewards: std::vec::Vec<i128> = farmers
+        .iter()
+        .map(|farmer| farm.client.harvest(farmer, &index))
+        .collect();
+
+    // Equal shares for everyone and the prize for exactly one of them
+    assert_eq!(rewards.iter().filter(|reward| **reward == share).count(), 4);
+    assert_eq!(
+        rewards
+            .iter()
+            .filter(|reward| **reward == share + prize)
+            .count(),
+        1
+    );
+}
@@ -1,5 +1,29 @@
 #![cfg(test)]
 
+mod bounty;
+mod cap;
+mod carry;
+mod config;
+mod counts;
+mod curves;
+mod floor;
+mod footprint;
+mod grace;
+mod history;
+mod interval;
+mod jackpot;
+mod ledgers;
+mod legacy;
+mod lottery;
 mod normalize;
+mod pending;
+mod schedule;
+mod shard;
+mod stats;
+mod streak;
+mod sweep;
+mod team;
 mod test;
+mod treasury;
 mod utils;
+mod weights;
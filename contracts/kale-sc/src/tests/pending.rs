@@ -0,0 +1,88 @@
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::{FarmConfig, Storage},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_pending_pails() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            grace_period: GRACE_PERIOD,
+            ..farm_config(&env)
+        }),
+    );
+
+    let farmer = Address::generate(&env);
+    let mut indexes = [0; 3];
+
+    // Plant three blocks but leave the middle one unworked
+    for (i, index) in indexes.iter_mut().enumerate() {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&farmer, &0);
+
+        *index = farm.index(&env);
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        if i != 1 {
+            farm.work(&env, &farmer, 1);
+        }
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 2);
+    }
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    let pails = farm.client.pending_pails(&farmer, &0, &u32::MAX);
+
+    assert_eq!(pails.len(), 3);
+    assert!(pails.get_unchecked(0).ready);
+    assert!(!pails.get_unchecked(1).worked && !pails.get_unchecked(1).ready);
+    // The last block is still in its grace period
+    assert!(pails.get_unchecked(2).worked && !pails.get_unchecked(2).ready);
+
+    // A page starts at the first pail planted at or after `from`
+    let page = farm.client.pending_pails(&farmer, &(indexes[0] + 1), &1);
+
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).index, indexes[1]);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    assert!(
+        farm.client
+            .pending_pails(&farmer, &0, &u32::MAX)
+            .get_unchecked(2)
+            .ready
+    );
+
+    farm.client.harvest(&farmer, &indexes[0]);
+
+    // Evicted pails are dropped the next time the farmer plants
+    env.as_contract(&farm.address, || {
+        env.storage()
+            .temporary()
+            .remove(&Storage::PailV2(farmer.clone(), indexes[1]));
+    });
+
+    farm.client.plant(&farmer, &0);
+
+    let index = farm.index(&env);
+    let pails = farm.client.pending_pails(&farmer, &0, &u32::MAX);
+
+    assert_eq!(pails.len(), 2);
+    assert_eq!(pails.get_unchecked(0).index, indexes[2]);
+    assert_eq!(pails.get_unchecked(1).index, index);
+    assert!(!pails.get_unchecked(1).ready);
+}
@@ -0,0 +1,158 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm, setup_farm_with_config},
+    types::{Era, FarmConfig, Schedule},
+    BLOCKS_PER_MONTH, BLOCK_INTERVAL, BLOCK_REWARD, BLOCK_SCALE, GRACE_PERIOD, INVERSE_DECAY_RATE,
+    V2_GENESIS_BLOCK,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Vec,
+};
+
+#[test]
+fn test_emission_eras() {
+    let env = setup_env();
+
+    // Halvings down to a tail, a linear ramp down and then a flat tail
+    let eras: Vec<Era> = vec![
+        &env,
+        Era {
+            start: 100,
+            reward: 1_000,
+            floor: 100,
+            schedule: Schedule::Halving(10),
+        },
+        Era {
+            start: 200,
+            reward: 500,
+            floor: 0,
+            schedule: Schedule::Linear(5, 50),
+        },
+        Era {
+            start: 300,
+            reward: 20,
+            floor: 0,
+            schedule: Schedule::Constant,
+        },
+    ];
+
+    let mut config = FarmConfig {
+        eras: eras.clone(),
+        ..farm_config(&env)
+    };
+
+    let farm = setup_farm_with_config(&env, Some(config.clone()));
+
+    // Blocks before the first era are paid its starting reward
+    assert_eq!(farm.client.block_reward(&0), 1_000);
+    assert_eq!(farm.client.block_reward(&100), 1_000);
+    assert_eq!(farm.client.block_reward(&119), 500);
+    assert_eq!(farm.client.block_reward(&130), 125);
+    assert_eq!(farm.client.block_reward(&199), 100);
+    assert_eq!(farm.client.block_reward(&200), 500);
+    assert_eq!(farm.client.block_reward(&214), 400);
+    assert_eq!(farm.client.block_reward(&299), 0);
+    assert_eq!(farm.client.block_reward(&300), 20);
+    assert_eq!(farm.client.block_reward(&u32::MAX), 20);
+
+    // Eras have to be in order
+    config.eras = vec![&env, eras.get_unchecked(1), eras.get_unchecked(0)];
+
+    let err = farm.client.try_set_config(&config).unwrap_err().unwrap();
+
+    assert_eq!(err, Errors::ConfigInvalid.into());
+}
+
+#[test]
+fn test_default_schedule() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(&env, None);
+
+    // Without eras the farm decays from `V2_GENESIS_BLOCK` like it always has
+    assert_eq!(farm.client.block_reward(&V2_GENESIS_BLOCK), BLOCK_REWARD);
+    assert_eq!(
+        farm.client
+            .block_reward(&(V2_GENESIS_BLOCK + BLOCKS_PER_MONTH)),
+        BLOCK_REWARD * (100 - 5) / 100
+    );
+
+    // and rounds every period exactly as the original per-period loop did, all the way down to nothing
+    let mut result = BLOCK_SCALE;
+
+    for period in 0..=600 {
+        assert_eq!(
+            farm.client
+                .block_reward(&(V2_GENESIS_BLOCK + period * BLOCKS_PER_MONTH)),
+            BLOCK_REWARD * result / BLOCK_SCALE
+        );
+
+        result = result * INVERSE_DECAY_RATE / BLOCK_SCALE;
+    }
+
+    assert_eq!(result, 0);
+}
+
+#[test]
+fn test_decay_periods() {
+    let env = setup_env();
+
+    let decay = |rate: i128| FarmConfig {
+        eras: vec![
+            &env,
+            Era {
+                start: 0,
+                reward: 1_000_000,
+                floor: 0,
+                schedule: Schedule::Decay(1, rate),
+            },
+        ],
+        ..farm_config(&env)
+    };
+
+    // Billions of periods resolve in a handful of squarings, whether the rate decays or not
+    let farm = setup_farm_with_config(&env, Some(decay(0)));
+
+    assert_eq!(farm.client.block_reward(&u32::MAX), 1_000_000);
+
+    farm.client.set_config(&decay(1));
+
+    assert_eq!(farm.client.block_reward(&1), 999_999);
+    assert_eq!(farm.client.block_reward(&u32::MAX), 995_714);
+}
+
+#[test]
+fn test_reward_snapshot() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+    let farmer = Address::generate(&env);
+
+    farm.client.plant(&farmer, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer, 1);
+
+    // Halving the reward mid-block only affects blocks opened after it
+    farm.client.set_config(&FarmConfig {
+        block_reward: BLOCK_REWARD / 2,
+        ..farm_config(&env)
+    });
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&farmer, &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    assert_eq!(farm.client.harvest(&farmer, &index), BLOCK_REWARD);
+    assert_eq!(
+        farm.client.block(&(index + 1)).block_reward,
+        BLOCK_REWARD / 2
+    );
+}
@@ -0,0 +1,133 @@
+use crate::{
+    contract_farm::get_shard_index,
+    tests::utils::{setup_env, setup_farm},
+    types::Storage,
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::ToXdr,
+    Address, Val, Vec,
+};
+
+#[test]
+fn test_shard_totals_exact() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let mut farmers = Vec::new(&env);
+
+    for i in 0..12 {
+        let farmer = Address::generate(&env);
+
+        farm.asset_admin.mint(&farmer, &(i * 1_0000000));
+        farmers.push_back(farmer);
+    }
+
+    // Two blocks so the second one has a real min/max envelope to normalize against
+    for _ in 0..2 {
+        let sequence = env.ledger().sequence();
+        let timestamp = env.ledger().timestamp();
+        let index = farm.index(&env);
+
+        for (i, farmer) in farmers.iter().enumerate() {
+            farm.client
+                .plant(&farmer, &(farm.asset.balance(&farmer) / (i as i128 + 1)));
+        }
+
+        for (i, farmer) in farmers.iter().enumerate() {
+            env.ledger().set_sequence_number(sequence + 1 + i as u32);
+
+            farm.work(&env, &farmer, (i % 3) as u32);
+        }
+
+        // Improving a pail later in the block moves both its gap and zeros
+        env.ledger().set_sequence_number(sequence + 30);
+
+        farm.work(&env, &farmers.get_unchecked(0), 4);
+
+        env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 31);
+
+        farm.client.plant(&Address::generate(&env), &0);
+
+        env.ledger()
+            .set_sequence_number(sequence + 31 + GRACE_PERIOD);
+
+        let block = farm.block(&env, index);
+        let mut rewards = 0;
+
+        for farmer in farmers.iter() {
+            rewards += farm.client.harvest(&farmer, &index);
+        }
+
+        // Every shard was folded into the block so the rewards add up to the whole emission, give or take rounding
+        assert_eq!(block.staked_total, 0);
+        assert!(rewards <= BLOCK_REWARD);
+        assert!(rewards >= BLOCK_REWARD - farmers.len() as i128);
+    }
+}
+
+#[test]
+fn test_block_written_once() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+
+    let sequence = env.ledger().sequence();
+
+    farm.client.plant(&farmer_1, &0);
+
+    let index = farm.index(&env);
+    let block = farm.block(&env, index);
+
+    farm.client.plant(&farmer_2, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer_1, 1);
+    farm.work(&env, &farmer_2, 1);
+
+    // `plant` and `work` only touch the farmer's shard, the block stays as it was created until rollover
+    assert_eq!(farm.block(&env, index), block);
+}
+
+#[test]
+fn test_shard_entry_size() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let sequence = env.ledger().sequence();
+    let farmer = Address::generate(&env);
+
+    farm.client.plant(&farmer, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer, 1);
+
+    let index = farm.index(&env);
+    let shard = get_shard_index(&env, &farmer);
+
+    let (block_size, shard_size) = env.as_contract(&farm.address, || {
+        let storage = env.storage().temporary();
+
+        (
+            storage
+                .get::<_, Val>(&Storage::BlockV2(index))
+                .unwrap()
+                .to_xdr(&env)
+                .len(),
+            storage
+                .get::<_, Val>(&Storage::Shard(index, shard))
+                .unwrap()
+                .to_xdr(&env)
+                .len(),
+        )
+    });
+
+    // Every `plant`, `work` and `harvest` rewrites its shard, so it carries none of the block's snapshots or histograms
+    assert!(shard_size * 2 < block_size);
+}
@@ -0,0 +1,71 @@
+use crate::{
+    tests::utils::{setup_env, setup_farm},
+    types::FarmerStats,
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_farmer_stats() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let farmer = Address::generate(&env);
+    let stake = 1_0000000;
+
+    farm.asset_admin.mint(&farmer, &(stake * 3));
+
+    assert_eq!(farm.client.farmer_stats(&farmer), FarmerStats::default());
+
+    let mut indexes = [0; 4];
+
+    // Work blocks 0, 1 and 3 but only plant block 2
+    for (i, index) in indexes.iter_mut().enumerate() {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&farmer, &if i == 2 { 0 } else { stake });
+
+        *index = farm.index(&env);
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        if i != 2 {
+            farm.work(&env, &farmer, 1);
+        }
+
+        // An improved hash counts towards the best zeros but not as another worked block
+        if i == 0 {
+            env.ledger().set_sequence_number(sequence + 2);
+
+            farm.work(&env, &farmer, 3);
+        }
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 3);
+    }
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward_0 = farm.client.harvest(&farmer, &indexes[0]);
+    let reward_1 = farm.client.harvest(&farmer, &indexes[1]);
+
+    let stats = farm.client.farmer_stats(&farmer);
+
+    assert_eq!(stats.blocks_planted, 4);
+    assert_eq!(stats.blocks_worked, 3);
+    assert_eq!(stats.blocks_harvested, 2);
+    assert_eq!(stats.total_staked, stake * 3);
+    assert_eq!(stats.total_reward, reward_0 + reward_1);
+    assert!(stats.best_zeros >= 3);
+    assert_eq!(stats.streak, 1);
+    assert_eq!(stats.longest_streak, 2);
+    assert_eq!(stats.last_worked_index, indexes[3]);
+    assert_eq!(stats.last_active_index, indexes[3]);
+}
@@ -0,0 +1,85 @@
+use crate::{
+    tests::utils::{farm_config, setup_env, setup_farm_with_config},
+    types::FarmConfig,
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD, STREAK_CAP,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+/// Run the same four blocks with the given `streak_cap` and return the last block's (veteran, newcomer, total) rewards
+fn farm_streak(streak_cap: u32) -> (i128, i128, i128) {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            streak_cap,
+            ..farm_config(&env)
+        }),
+    );
+
+    let veteran = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let helper = Address::generate(&env);
+    let stake = 1_0000000;
+
+    farm.asset_admin.mint(&veteran, &(stake * 4));
+    farm.asset_admin.mint(&newcomer, &stake);
+
+    let mut index = 0;
+
+    // The veteran works every block, the newcomer only shows up for the last one with the same stake
+    for i in 0..4 {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&veteran, &stake);
+        farm.client.plant(&helper, &0);
+
+        if i == 3 {
+            farm.client.plant(&newcomer, &stake);
+        }
+
+        index = farm.index(&env);
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        farm.work(&env, &veteran, 1);
+        farm.work(&env, &helper, 1);
+
+        if i == 3 {
+            farm.work(&env, &newcomer, 1);
+        }
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 2);
+    }
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let veteran_reward = farm.client.harvest(&veteran, &index);
+    let newcomer_reward = farm.client.harvest(&newcomer, &index);
+    let helper_reward = farm.client.harvest(&helper, &index);
+
+    (
+        veteran_reward,
+        newcomer_reward,
+        veteran_reward + newcomer_reward + helper_reward,
+    )
+}
+
+#[test]
+fn test_streak_bonus() {
+    let (veteran, newcomer, total) = farm_streak(STREAK_CAP);
+    let (veteran_flat, newcomer_flat, total_flat) = farm_streak(0);
+
+    // The bonus shifts the reward towards the veteran without minting anything extra
+    assert!(veteran > veteran_flat);
+    assert!(newcomer < newcomer_flat);
+    assert!((BLOCK_REWARD - 3..=BLOCK_REWARD).contains(&total));
+    assert!((BLOCK_REWARD - 3..=BLOCK_REWARD).contains(&total_flat));
+}
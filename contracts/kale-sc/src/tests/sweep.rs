@@ -0,0 +1,114 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm, setup_farm_with_config},
+    types::FarmConfig,
+    BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_sweep_expired() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    let harvester = Address::generate(&env);
+    let forgetter = Address::generate(&env);
+
+    for farmer in [&harvester, &forgetter] {
+        farm.client.plant(farmer, &0);
+    }
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    for farmer in [&harvester, &forgetter] {
+        farm.work(&env, farmer, 1);
+    }
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&harvester, &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward = farm.client.harvest(&harvester, &index);
+
+    // The forgetter's share can't be swept while they could still harvest it
+    let err = farm.client.try_sweep_expired(&index).unwrap_err().unwrap();
+
+    assert_eq!(err, Errors::BlockNotExpired.into());
+
+    farm.client.remove_block(&index);
+
+    assert_eq!(farm.client.sweep_expired(&index), BLOCK_REWARD - reward);
+
+    let err = farm.client.try_sweep_expired(&index).unwrap_err().unwrap();
+
+    assert_eq!(err, Errors::UnclaimedMissing.into());
+
+    // The swept share is paid out by the next block opened
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&harvester, &0);
+
+    assert_eq!(farm.block(&env, index + 2).carry, BLOCK_REWARD - reward);
+}
+
+#[test]
+fn test_sweep_after_grace() {
+    let env = setup_env();
+    let farm = setup_farm_with_config(
+        &env,
+        Some(FarmConfig {
+            grace_period: GRACE_PERIOD,
+            ..farm_config(&env)
+        }),
+    );
+
+    let sequence = env.ledger().sequence();
+    let index = farm.index(&env);
+
+    let late = Address::generate(&env);
+    let forgetter = Address::generate(&env);
+    let stake = 10_0000000;
+
+    farm.asset_admin.mint(&late, &stake);
+    farm.client.plant(&late, &stake);
+    farm.client.plant(&forgetter, &0);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    let (nonce, hash) = farm.find(&env, &late, 1);
+
+    farm.work(&env, &forgetter, 1);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+    env.ledger().set_sequence_number(sequence + 2);
+
+    farm.client.plant(&forgetter, &0);
+
+    // The late hash reclaims its stake from the block after its pool was recorded
+    farm.client.work(&late, &hash, &nonce);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward = farm.client.harvest(&late, &index);
+
+    assert_eq!(farm.asset.balance(&late), reward + stake);
+
+    farm.client.remove_block(&index);
+
+    // so only the forgetter's share of the block reward is swept, not the stake already returned
+    assert_eq!(farm.client.sweep_expired(&index), BLOCK_REWARD - reward);
+}
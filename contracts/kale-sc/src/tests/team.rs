@@ -0,0 +1,120 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{setup_env, setup_farm},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+#[test]
+fn test_team() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let team = Address::generate(&env);
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+    let farmer_3 = Address::generate(&env);
+    let farmer_4 = Address::generate(&env);
+
+    let stake_1 = 30_0000000;
+    let stake_2 = 10_0000000;
+
+    farm.asset_admin.mint(&farmer_1, &stake_1);
+    farm.asset_admin.mint(&farmer_2, &stake_2);
+
+    farm.client.create_team(&team);
+    farm.client.join_team(&farmer_1, &team);
+    farm.client.join_team(&farmer_2, &team);
+    farm.client.join_team(&farmer_3, &team);
+
+    let err = farm
+        .client
+        .try_join_team(&farmer_1, &team)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::TeamMemberExists.into());
+
+    let err = farm
+        .client
+        .try_plant_team(&farmer_4, &0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::TeamMemberMissing.into());
+
+    farm.client.plant_team(&farmer_1, &stake_1);
+    farm.client.plant_team(&farmer_2, &stake_2);
+    farm.client.plant(&farmer_4, &0);
+
+    let err = farm
+        .client
+        .try_plant_team(&farmer_1, &0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::PailExists.into());
+
+    let index = farm.index(&env);
+
+    // Leaving doesn't forfeit what's already been planted
+    farm.client.leave_team(&farmer_2);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    // The team address is the farmer in the hash, any member can submit it
+    farm.work(&env, &team, 1);
+    farm.work(&env, &farmer_4, 0);
+
+    let err = farm
+        .client
+        .try_plant_team(&farmer_3, &0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::PailWorked.into());
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+
+    farm.client.plant(&farmer_4, &0);
+
+    // Wait out the grace period for late `work` on the closed block
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward = farm.client.harvest(&team, &index);
+
+    assert!(reward > 0);
+    assert_eq!(farm.asset.balance(&team), 0);
+    assert_eq!(farm.asset.balance(&farmer_1), stake_1 + reward * 3 / 4);
+    assert_eq!(farm.asset.balance(&farmer_2), stake_2 + reward / 4);
+    assert_eq!(farm.asset.balance(&farmer_3), 0);
+}
+
+#[test]
+fn test_team_solo_pail() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let team = Address::generate(&env);
+    let farmer = Address::generate(&env);
+
+    farm.client.create_team(&team);
+    farm.client.join_team(&farmer, &team);
+
+    // A solo pail planted by the team address can't be hijacked by its members
+    farm.client.plant(&team, &0);
+
+    let err = farm
+        .client
+        .try_plant_team(&farmer, &0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::PailExists.into());
+}
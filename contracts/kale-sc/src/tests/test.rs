@@ -4,8 +4,8 @@ extern crate std;
 use crate::{
     errors::Errors,
     tests::utils::find_nonce_and_hash,
-    types::{Block, Storage},
-    Contract, ContractClient, BLOCK_INTERVAL, BLOCK_REWARD,
+    types::{Block, FarmConfig, Storage},
+    Contract, ContractClient, BLOCK_INTERVAL, BLOCK_REWARD, GRACE_PERIOD,
 };
 use soroban_sdk::{
     testutils::{Address as _, BytesN as _, EnvTestConfig, Ledger},
@@ -38,7 +38,8 @@ fn test() {
     let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
     let asset_client = token::Client::new(&env, &asset_address);
 
-    let farm_address: Address = env.register(Contract, (&homesteader, &asset_address));
+    let farm_address: Address =
+        env.register(Contract, (&homesteader, &asset_address, None::<FarmConfig>));
     let farm_client = ContractClient::new(&env, &farm_address);
 
     asset_homesteader.set_admin(&farm_address);
@@ -74,7 +75,7 @@ fn test() {
         block = env
             .storage()
             .temporary()
-            .get::<Storage, Block>(&Storage::Block(index.unwrap_or(0)));
+            .get::<Storage, Block>(&Storage::BlockV2(index.unwrap_or(0)));
     });
 
     let index = index.unwrap_or(0);
@@ -151,7 +152,7 @@ fn test() {
         let block = env
             .storage()
             .temporary()
-            .get::<Storage, Block>(&Storage::Block(index))
+            .get::<Storage, Block>(&Storage::BlockV2(index))
             .unwrap();
 
         println!("{} {:?}", index, block);
@@ -162,6 +163,9 @@ fn test() {
 
     farm_client.plant(&farmer_1, &0);
 
+    env.ledger()
+        .set_sequence_number(sequence + 20 + GRACE_PERIOD);
+
     farm_client.harvest(&farmer_1, &index);
     farm_client.harvest(&farmer_2, &index);
     farm_client.harvest(&farmer_3, &index);
@@ -195,7 +199,7 @@ fn test() {
         let block = env
             .storage()
             .temporary()
-            .get::<Storage, Block>(&Storage::Block(index))
+            .get::<Storage, Block>(&Storage::BlockV2(index))
             .unwrap();
 
         println!("{} {:?}", index, block);
@@ -224,7 +228,8 @@ fn test_decay() {
     let asset_address = asset_sac.address();
     let asset_homesteader = token::StellarAssetClient::new(&env, &asset_address);
 
-    let farm_address: Address = env.register(Contract, (&homesteader, &asset_address));
+    let farm_address: Address =
+        env.register(Contract, (&homesteader, &asset_address, None::<FarmConfig>));
     let farm_client = ContractClient::new(&env, &farm_address);
 
     asset_homesteader.set_admin(&farm_address);
@@ -259,6 +264,9 @@ fn test_decay() {
         .set_timestamp((timestamp + BLOCK_INTERVAL) * (iterations + 1) as u64);
     farm_client.plant(&farmer_1, &0);
 
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
     env.cost_estimate().budget().reset_unlimited();
 
     let reward = farm_client.harvest(&farmer_1, &index);
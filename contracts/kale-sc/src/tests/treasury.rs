@@ -0,0 +1,89 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{setup_env, setup_farm},
+    BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, FromVal, IntoVal,
+};
+
+#[test]
+fn test_treasury_fee() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let treasury = Address::generate(&env);
+    let farmer_1 = Address::generate(&env);
+    let farmer_2 = Address::generate(&env);
+
+    let stake_1 = 1_0000000;
+    let stake_2 = 0;
+
+    farm.asset_admin.mint(&farmer_1, &stake_1);
+
+    // 10%
+    farm.client.set_treasury(&treasury, &1_000);
+
+    let sequence = env.ledger().sequence();
+    let timestamp = env.ledger().timestamp();
+
+    farm.client.plant(&farmer_1, &stake_1);
+    farm.client.plant(&farmer_2, &stake_2);
+
+    let index = farm.index(&env);
+
+    env.ledger().set_sequence_number(sequence + 1);
+
+    farm.work(&env, &farmer_1, 1);
+    farm.work(&env, &farmer_2, 0);
+
+    env.ledger().set_timestamp(timestamp + BLOCK_INTERVAL);
+
+    farm.client.plant(&farmer_1, &0);
+
+    // Wait out the grace period for late `work` on the closed block
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let reward_1 = farm.client.harvest(&farmer_1, &index);
+    let (_, topics, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(contract, ..)| *contract == farm.address)
+        .unwrap();
+    let fee_1 = farm.asset.balance(&treasury);
+
+    // The treasury only ever takes its cut from the reward, never from the returned stake
+    assert!(fee_1 > 0);
+    assert_eq!(farm.asset.balance(&farmer_1), stake_1 + reward_1);
+    assert_eq!(fee_1, (reward_1 + fee_1) / 10);
+
+    assert_eq!(
+        topics,
+        (symbol_short!("treasury"), farmer_1.clone(), index).into_val(&env)
+    );
+    assert_eq!(i128::from_val(&env, &data), fee_1);
+
+    let reward_2 = farm.client.harvest(&farmer_2, &index);
+    let fee_2 = farm.asset.balance(&treasury) - fee_1;
+
+    assert_eq!(farm.asset.balance(&farmer_2), reward_2);
+    assert_eq!(fee_2, (reward_2 + fee_2) / 10);
+}
+
+#[test]
+fn test_treasury_fee_too_high() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let err = farm
+        .client
+        .try_set_treasury(&Address::generate(&env), &10_001)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(err, Errors::TreasuryFeeTooHigh.into());
+}
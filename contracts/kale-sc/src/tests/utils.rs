@@ -3,13 +3,20 @@ extern crate std;
 
 use ed25519_dalek::Keypair;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{testutils::Address as _, xdr::ToXdr, Address, Bytes, BytesN, Env};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Ledger},
+    token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec,
+};
 use stellar_strkey::{ed25519, Strkey};
 use tiny_keccak::{Hasher, Keccak};
 
 use crate::{
-    types::{Block, Pail},
-    BLOCKS_PER_MONTH, BLOCK_REWARD, BLOCK_SCALE, INVERSE_DECAY_RATE, V2_GENESIS_BLOCK,
+    default_farm_config,
+    types::{Block, Curve, FarmConfig, Pail, Storage},
+    Contract, ContractClient, BASIS_POINTS, BLOCKS_PER_MONTH, BLOCK_INTERVAL, BLOCK_REWARD,
+    BLOCK_SCALE, INVERSE_DECAY_RATE, STAKE_BUCKETS, V2_GENESIS_BLOCK, ZEROS_BUCKETS,
 };
 
 #[test]
@@ -54,6 +61,34 @@ fn test_generate_reward() {
         normalized_total: 230227940395912,
         staked_total: 15000000,
         timestamp: 0,
+        planted_count: 0,
+        worked_count: 0,
+        harvested_count: 0,
+        total_reward_minted: 0,
+        zeros_total: 0,
+        gap_weight: BASIS_POINTS,
+        stake_weight: BASIS_POINTS,
+        zeros_weight: BASIS_POINTS,
+        stake_curve: Curve::Linear,
+        zeros_curve: Curve::Linear,
+        gap_cap: 0,
+        planted_total: 0,
+        stake_cap: i128::MAX,
+        zeros_counts: Vec::from_array(&env, [0; ZEROS_BUCKETS as usize]),
+        stake_counts: Vec::from_array(&env, [0; STAKE_BUCKETS as usize]),
+        zeros_floor: 0,
+        interval: BLOCK_INTERVAL,
+        ledgers: 0,
+        carry: 0,
+        block_reward: BLOCK_REWARD,
+        lottery_share: 0,
+        lottery_shard: 0,
+        lottery_ticket: u32::MAX,
+        jackpot_share: 0,
+        best_zeros: 0,
+        best_sequence: 0,
+        best_shard: 0,
+        best_ticket: u32::MAX,
     };
 
     let pail = Pail {
@@ -61,6 +96,8 @@ fn test_generate_reward() {
         gap: Some(40),
         stake: 10000,
         zeros: Some(7),
+        streak: 0,
+        ticket: None,
     };
 
     let (normalized_gap, normalized_stake, normalized_zeros) = generate_normalizations_v2(
@@ -91,6 +128,8 @@ fn test_zero_harvest() {
         gap: Some(0),
         stake: 0,
         zeros: Some(0),
+        streak: 0,
+        ticket: None,
     };
 
     let block = Block {
@@ -104,6 +143,34 @@ fn test_zero_harvest() {
         normalized_total: 1114640798 * 3 * 100,
         staked_total: 0,
         timestamp: 0,
+        planted_count: 0,
+        worked_count: 0,
+        harvested_count: 0,
+        total_reward_minted: 0,
+        zeros_total: 0,
+        gap_weight: BASIS_POINTS,
+        stake_weight: BASIS_POINTS,
+        zeros_weight: BASIS_POINTS,
+        stake_curve: Curve::Linear,
+        zeros_curve: Curve::Linear,
+        gap_cap: 0,
+        planted_total: 0,
+        stake_cap: i128::MAX,
+        zeros_counts: Vec::from_array(&env, [0; ZEROS_BUCKETS as usize]),
+        stake_counts: Vec::from_array(&env, [0; STAKE_BUCKETS as usize]),
+        zeros_floor: 0,
+        interval: BLOCK_INTERVAL,
+        ledgers: 0,
+        carry: 0,
+        block_reward: BLOCK_REWARD,
+        lottery_share: 0,
+        lottery_shard: 0,
+        lottery_ticket: u32::MAX,
+        jackpot_share: 0,
+        best_zeros: 0,
+        best_sequence: 0,
+        best_shard: 0,
+        best_ticket: u32::MAX,
     };
 
     let (normalized_gap, normalized_stake, normalized_zeros) = generate_normalizations_v1(
@@ -210,6 +277,25 @@ fn test_address_lengths() {
     );
 }
 
+#[test]
+fn test_generate_keccak() {
+    let env: Env = Env::default();
+
+    let index = 7;
+    let entropy = BytesN::from_array(&env, &[1; 32]);
+    let farmer = Address::generate(&env);
+
+    // Bumping the nonce in place must hash exactly what the contract hashes for that nonce
+    let mut hash_array = generate_hash(&env, &index, &0, &entropy, &farmer);
+
+    for nonce in [0, 1, u64::MAX] {
+        assert_eq!(
+            generate_keccak(&mut hash_array, &nonce),
+            crate::contract_farm::generate_hash(&env, &index, &nonce, &entropy, &farmer).to_array()
+        );
+    }
+}
+
 #[test]
 fn test_count_bytes() {
     let env = Env::default();
@@ -295,6 +381,93 @@ fn test_unsafe_stuff() {
     // }
 }
 
+pub struct Farm<'a> {
+    pub address: Address,
+    pub client: ContractClient<'a>,
+    pub asset: token::Client<'a>,
+    pub asset_admin: token::StellarAssetClient<'a>,
+}
+
+/// The farm's default config, tests override only the fields they exercise with `..farm_config(&env)`
+pub fn farm_config(env: &Env) -> FarmConfig {
+    default_farm_config(env)
+}
+
+pub fn setup_env() -> Env {
+    let mut env: Env = Env::default();
+
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    env.ledger().set_min_temp_entry_ttl(17280);
+    env.ledger().set_min_persistent_entry_ttl(2073600);
+
+    env.mock_all_auths();
+
+    env
+}
+
+pub fn setup_farm(env: &Env) -> Farm<'_> {
+    setup_farm_with_config(env, None)
+}
+
+pub fn setup_farm_with_config(env: &Env, config: Option<FarmConfig>) -> Farm<'_> {
+    let homesteader: Address = Address::generate(env);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(homesteader.clone());
+    let asset_address = asset_sac.address();
+    let asset_admin = token::StellarAssetClient::new(env, &asset_address);
+    let asset = token::Client::new(env, &asset_address);
+
+    let address: Address = env.register(Contract, (&homesteader, &asset_address, config));
+    let client = ContractClient::new(env, &address);
+
+    asset_admin.set_admin(&address);
+
+    Farm {
+        address,
+        client,
+        asset,
+        asset_admin,
+    }
+}
+
+impl Farm<'_> {
+    pub fn index(&self, env: &Env) -> u32 {
+        env.as_contract(&self.address, || {
+            env.storage()
+                .instance()
+                .get::<Storage, u32>(&Storage::FarmIndex)
+                .unwrap_or(0)
+        })
+    }
+
+    pub fn block(&self, env: &Env, index: u32) -> Block {
+        env.as_contract(&self.address, || {
+            env.storage()
+                .temporary()
+                .get::<Storage, Block>(&Storage::BlockV2(index))
+                .unwrap()
+        })
+    }
+
+    /// Find a nonce and hash with at least `zeros` leading zeros for the current block
+    pub fn find(&self, env: &Env, farmer: &Address, zeros: u32) -> (u64, BytesN<32>) {
+        let index = self.index(env);
+        let block = self.block(env, index);
+
+        find_nonce_and_hash(env, &index, &block.entropy, farmer, zeros)
+    }
+
+    /// Find a hash with at least `zeros` leading zeros for the current block and submit it
+    pub fn work(&self, env: &Env, farmer: &Address, zeros: u32) -> u32 {
+        let (nonce, hash) = self.find(env, farmer, zeros);
+
+        self.client.work(farmer, &hash, &nonce)
+    }
+}
+
 pub fn find_nonce_and_hash(
     env: &Env,
     index: &u32,
@@ -332,7 +505,8 @@ pub fn find_nonce_and_hash(
 fn generate_keccak(hash_b: &mut [u8; 76], nonce: &u64) -> [u8; 32] {
     let mut hash = [0u8; 32];
 
-    hash_b[8..16].copy_from_slice(&nonce.to_be_bytes());
+    // The nonce sits right after the 4 byte index, the same layout `generate_hash` and the contract use
+    hash_b[4..12].copy_from_slice(&nonce.to_be_bytes());
 
     let mut keccak = Keccak::v256();
     keccak.update(hash_b);
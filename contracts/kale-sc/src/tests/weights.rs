@@ -0,0 +1,87 @@
+use crate::{
+    errors::Errors,
+    tests::utils::{farm_config, setup_env, setup_farm},
+    types::FarmConfig,
+    BASIS_POINTS, BLOCK_INTERVAL, GRACE_PERIOD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn weighted_config(env: &Env, gap_weight: u32, stake_weight: u32, zeros_weight: u32) -> FarmConfig {
+    FarmConfig {
+        gap_weight,
+        stake_weight,
+        zeros_weight,
+        ..farm_config(env)
+    }
+}
+
+#[test]
+fn test_weights_snapshot() {
+    let env = setup_env();
+    let farm = setup_farm(&env);
+
+    let staker = Address::generate(&env);
+    let hasher = Address::generate(&env);
+    let stake = 1_0000000;
+
+    farm.asset_admin.mint(&staker, &(stake * 2));
+
+    let mut indexes = [0; 2];
+
+    for (i, index) in indexes.iter_mut().enumerate() {
+        let sequence = env.ledger().sequence();
+
+        farm.client.plant(&staker, &stake);
+        farm.client.plant(&hasher, &0);
+
+        *index = farm.index(&env);
+
+        // Only stake counts from here on (no streak bonus either) but the open block keeps the weights it was opened with
+        if i == 0 {
+            farm.client
+                .set_config(&weighted_config(&env, 0, BASIS_POINTS, 0));
+        }
+
+        env.ledger().set_sequence_number(sequence + 1);
+
+        farm.work(&env, &staker, 0);
+        farm.work(&env, &hasher, 4);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + BLOCK_INTERVAL);
+        env.ledger().set_sequence_number(sequence + 2);
+    }
+
+    farm.client.plant(&Address::generate(&env), &0);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + GRACE_PERIOD);
+
+    let block_0 = farm.block(&env, indexes[0]);
+    let block_1 = farm.block(&env, indexes[1]);
+
+    assert_eq!(block_0.gap_weight, BASIS_POINTS);
+    assert_eq!(block_0.zeros_weight, BASIS_POINTS);
+    assert_eq!(block_1.gap_weight, 0);
+    assert_eq!(block_1.zeros_weight, 0);
+
+    farm.client.harvest(&staker, &indexes[0]);
+    farm.client.harvest(&hasher, &indexes[0]);
+
+    // With only the stake term weighted the hasher's extra zeros are worth nothing
+    let staker_reward = farm.client.harvest(&staker, &indexes[1]);
+    let hasher_reward = farm.client.harvest(&hasher, &indexes[1]);
+
+    assert!(hasher_reward < staker_reward / 1_000);
+
+    assert_eq!(
+        farm.client
+            .try_set_config(&weighted_config(&env, 0, 0, 0))
+            .unwrap_err()
+            .unwrap(),
+        Errors::ConfigInvalid.into()
+    );
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, Address, BytesN};
+pub use kale_types::{Curve, Era, FarmConfig, FarmerStats, PendingPail, Schedule, StakeCap};
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -13,6 +14,102 @@ pub struct Block {
     pub entropy: BytesN<32>,
     pub staked_total: i128,
     pub normalized_total: i128,
+    pub planted_count: u32,
+    pub worked_count: u32,
+    pub harvested_count: u32, // only moves once the block has closed, and only in the harvesting farmer's shard
+    pub total_reward_minted: i128, // rewards and treasury fees, not returned stakes
+    pub zeros_total: u32,
+    pub gap_weight: u32, // the config's weights when the block was opened
+    pub stake_weight: u32,
+    pub zeros_weight: u32,
+    pub stake_curve: Curve, // the config's scoring when the block was opened
+    pub zeros_curve: Curve,
+    pub gap_cap: u32,
+    pub planted_total: i128, // every stake planted, `staked_total` loses them again as pails are worked
+    pub stake_cap: i128, // largest stake a pail can hold, resolved from the config when the block was opened
+    pub zeros_counts: Vec<u32>, // worked pails by zeros, always ZEROS_BUCKETS long so the entry size never changes
+    pub stake_counts: Vec<u32>, // planted pails by stake bit length, always STAKE_BUCKETS long
+    pub zeros_floor: u32, // fewest zeros `work` accepts, resolved from the config when the block was opened
+    pub interval: u64, // seconds the block stays open, resolved from the config when the block was opened
+    pub ledgers: u32,  // ledgers the block stays open instead, 0 when it closes on `interval`
+    pub carry: i128,   // leftover pools of earlier blocks added to this block's reward
+    pub block_reward: i128, // decayed reward for the block's interval, resolved from the config when the block was opened
+    pub lottery_share: u32, // BASIS_POINTS of the block reward set aside for the lottery
+    pub lottery_shard: u32, // shard of the winning ticket
+    pub lottery_ticket: u32, // drawn at rollover, u32::MAX until then or when nobody worked
+    pub jackpot_share: u32, // BASIS_POINTS of the block reward paid to the best hash
+    // The best hash is kept as its pail's shard and ticket, an `Address` would break the fixed entry size
+    pub best_zeros: u32,
+    pub best_sequence: u32, // ledger the best hash was submitted, the earliest wins a tie
+    pub best_shard: u32,
+    pub best_ticket: u32, // u32::MAX when nobody worked
+}
+
+// A block's running totals, split across BLOCK_SHARDS entries so concurrent `plant`, `work` and `harvest` calls only collide within a shard
+// Rollover folds them into the `Block`, which alone keeps the config snapshots and the full histograms
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shard {
+    pub min_gap: u32,
+    pub min_stake: i128,
+    pub min_zeros: u32,
+    pub max_gap: u32,
+    pub max_stake: i128,
+    pub max_zeros: u32,
+    pub entropy: BytesN<32>, // hash of the shard's last `work`
+    pub staked_total: i128,
+    pub normalized_total: i128,
+    pub planted_total: i128,
+    pub planted_count: u32,
+    pub worked_count: u32, // also numbers the shard's tickets
+    pub harvested_count: u32,
+    pub total_reward_minted: i128,
+    pub zeros_total: u32,
+    // Pails tallied by histogram bucket as big endian u16s, a fraction of a `Vec<u32>` and still a fixed size
+    pub zeros_tally: BytesN<32>,  // ZEROS_BUCKETS buckets
+    pub stake_tally: BytesN<128>, // STAKE_BUCKETS buckets
+    pub best_zeros: u32,
+    pub best_sequence: u32,
+    pub best_ticket: u32, // u32::MAX when nobody in the shard worked
+}
+
+// The original `Block`, still stored under `FarmBlock` and `Block(index)` by a farm that's been upgraded. Never change it
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegacyBlock {
+    pub timestamp: u64,
+    pub min_gap: u32,
+    pub min_stake: i128,
+    pub min_zeros: u32,
+    pub max_gap: u32,
+    pub max_stake: i128,
+    pub max_zeros: u32,
+    pub entropy: BytesN<32>,
+    pub staked_total: i128,
+    pub normalized_total: i128,
+}
+
+// Persistent record of a closed block, kept long after its temporary `Block` has been evicted
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockSummary {
+    pub index: u32,
+    pub timestamp: u64,
+    pub entropy: BytesN<32>,
+    pub staked_total: i128,
+    pub normalized_total: i128,
+    pub reward: i128, // decayed block reward plus its carry, the unreclaimed `staked_total` is shared on top of it
+    pub participants: u32, // farmers who worked the block
+}
+
+// The original `Pail`, still stored under `Pail(farmer, index)` for blocks opened before the upgrade. Never change it
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegacyPail {
+    pub sequence: u32,
+    pub gap: Option<u32>,
+    pub stake: i128,
+    pub zeros: Option<u32>,
 }
 
 #[contracttype]
@@ -22,16 +119,57 @@ pub struct Pail {
     pub gap: Option<u32>,
     pub stake: i128,
     pub zeros: Option<u32>,
+    pub streak: u32,         // bonus in basis points, fixed when the pail is planted
+    pub ticket: Option<u32>, // lottery ticket within its shard, given on the first non-late `work`
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounty {
+    pub amount: i128,
+    pub zeros: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BountyCommit {
+    pub commitment: BytesN<32>, // keccak256 of the worker's address xdr and the hash
+    pub sequence: u32, // ledger the commitment was made, the hash can only be revealed after it
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Treasury {
+    pub address: Address,
+    pub fee: u32, // basis points of each harvested reward
 }
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum Storage {
-    Homesteader,        // : address
-    HomesteadAsset,     // : address
-    FarmIndex,          // : u32
-    FarmBlock,          // : Block
+    Homesteader,                         // : address
+    HomesteadAsset,                      // : address
+    FarmIndex,                           // : u32
+    FarmBlock,          // : LegacyBlock (folded into the first rollover after the upgrade)
     FarmPaused,         // : bool
-    Block(u32),         // (index) : Block
-    Pail(Address, u32), // (farmer, index) : Pail
+    FarmRollover,       // : u32 (sequence the current block was opened at)
+    FarmTreasury,       // : Treasury
+    FarmConfig,         // : FarmConfig
+    FarmCarry,          // : i128 (leftover pools waiting for the next block)
+    FarmLegacy,         // : u32 (last block index opened before the upgrade)
+    Block(u32),         // (index) : LegacyBlock
+    BlockV2(u32),       // (index) : Block
+    Shard(u32, u32),    // (index, shard) : Shard
+    BlockSummary(u32),  // (index) : BlockSummary
+    Unclaimed(u32),     // (index) : i128 (pool not yet harvested from a closed block)
+    Claimed(u32, u32), // (index, shard) : i128 (rewards harvested from a closed block by the shard's farmers)
+    Pail(Address, u32), // (farmer, index) : LegacyPail
+    PailV2(Address, u32), // (farmer, index) : Pail
+    FarmerStats(Address), // (farmer) : FarmerStats
+    FarmerPails(Address), // (farmer) : Vec<u32> (indexes with an unharvested pail)
+    Bounty(Address, u32), // (farmer, index) : Bounty (persistent, the escrow has to outlive its block)
+    BountyCommit(Address, Address, u32), // (worker, farmer, index) : BountyCommit
+    Team(Address),        // (team) : Vec<Address>
+    TeamMember(Address),  // (farmer) : address
+    TeamPail(Address, u32), // (team, index) : Map<Address, i128>
 }
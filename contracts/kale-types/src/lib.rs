@@ -0,0 +1,94 @@
+#![no_std]
+
+// The farm's config and the views other contracts read, shared with `kale-sc` without pulling in its entrypoints
+use soroban_sdk::{contracttype, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingPail {
+    pub index: u32,
+    pub stake: i128,
+    pub worked: bool,
+    pub ready: bool, // `harvest` would pay out right now
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FarmerStats {
+    pub blocks_planted: u32,
+    pub blocks_worked: u32,
+    pub blocks_harvested: u32,
+    pub total_staked: i128,
+    pub total_reward: i128,
+    pub best_zeros: u32,
+    pub streak: u32, // consecutive blocks worked, ending at `last_worked_index`
+    pub longest_streak: u32,
+    pub last_worked_index: u32,
+    pub last_active_index: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Curve {
+    Linear = 0,
+    Sqrt = 1, // diminishing returns, a whale's stake no longer stretches the whole range
+    Log = 2,
+    Exp = 3, // each extra zero nibble is worth 16x, as it takes 16x the hashing
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum StakeCap {
+    None,
+    Absolute(i128),
+    // BASIS_POINTS multiple of the previous block's median stake, rounded up to the top of its `stake_counts` bucket
+    Median(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schedule {
+    Constant,
+    Decay(u32, i128), // compounds `rate` (BLOCK_SCALE is 100%) every `period` blocks
+    Halving(u32),     // halves every `period` blocks
+    Linear(u32, i128), // drops by `step` every `period` blocks
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Era {
+    pub start: u32,   // first block index of the era
+    pub reward: i128, // block reward at `start`
+    pub floor: i128,  // tail emission the reward never drops below
+    pub schedule: Schedule,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FarmConfig {
+    pub block_interval: u64, // seconds
+    pub block_reward: i128,
+    pub decay_period: u32,  // blocks between each decay step
+    pub decay_rate: i128,   // BLOCK_SCALE is 100%
+    pub genesis_block: u32, // block the decay schedule starts counting from
+    pub grace_period: u32,  // ledgers after a rollover the previous block still accepts `work`
+    pub streak_cap: u32,    // consecutive worked blocks for the full streak bonus, 0 disables it
+    pub gap_weight: u32,    // BASIS_POINTS is 100%
+    pub stake_weight: u32,  // BASIS_POINTS is 100%
+    pub zeros_weight: u32,  // BASIS_POINTS is 100%
+    pub stake_curve: Curve, // `Exp` isn't allowed for stake
+    pub zeros_curve: Curve,
+    pub gap_cap: u32, // gaps above this many ledgers score the same, 0 disables it
+    pub stake_cap: StakeCap,
+    pub zeros_target: u32, // BASIS_POINTS share of the previous block's planters that would have cleared the floor, 0 disables it
+    pub zeros_floor_min: u32,
+    pub zeros_floor_max: u32,
+    pub activity_target: u32, // BASIS_POINTS plants and works per ledger the interval adapts towards, 0 keeps it at `block_interval`
+    pub interval_min: u64,    // seconds
+    pub interval_max: u64,    // seconds
+    pub block_ledgers: u32, // close blocks after this many ledgers instead of `block_interval` seconds, 0 keeps the timestamp
+    pub eras: Vec<Era>, // ordered by `start`, empty runs the decay schedule above from `genesis_block`
+    pub lottery_share: u32, // BASIS_POINTS of each block reward drawn for a single worker, 0 disables it
+    pub jackpot_share: u32, // BASIS_POINTS of each block reward for the worker with the most zeros, 0 disables it
+}